@@ -1,19 +1,28 @@
+use std::{sync::Arc, time::Instant};
+
 use parking_lot::Mutex;
 
 use crate::{
     audio::RecordingSession,
     config::{default_config, load_or_init_config},
+    context_cache::WhisperContextCache,
+    jobs::JobQueue,
     types::{AppConfig, AppStatus, RecordingState},
 };
 
 pub struct AppState {
     inner: Mutex<AppStateInner>,
+    /// Shared with the blocking threads that load models and run inference.
+    pub contexts: Arc<WhisperContextCache>,
 }
 
 pub struct AppStateInner {
     pub status: AppStatus,
     pub config: AppConfig,
     pub active_session: Option<RecordingSession>,
+    pub jobs: JobQueue,
+    /// Raw text of the last delivered dictation and when it finished.
+    pub last_transcript: Option<(String, Instant)>,
 }
 
 impl AppState {
@@ -34,7 +43,10 @@ impl AppState {
                 },
                 config,
                 active_session: None,
+                jobs: JobQueue::default(),
+                last_transcript: None,
             }),
+            contexts: Arc::new(WhisperContextCache::new()),
         }
     }
 
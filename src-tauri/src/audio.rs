@@ -5,23 +5,58 @@ use std::sync::{mpsc, Arc};
 use std::thread::JoinHandle;
 use uuid::Uuid;
 
+pub const TARGET_SAMPLE_RATE: u32 = 16_000;
+/// 20 ms analysis frames at the whisper sample rate.
+const ENERGY_FRAME_SAMPLES: usize = 320;
+const SILENCE_RMS_FLOOR: f32 = 0.01;
+
 struct CapturedAudio {
     samples: Vec<f32>,
     sample_rate: u32,
     channels: u16,
 }
 
+/// Read-only view of a session's raw capture buffer while recording is still in progress.
+#[derive(Clone)]
+pub struct AudioTap {
+    samples: Arc<Mutex<Vec<f32>>>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl AudioTap {
+    /// Number of 16 kHz mono samples captured so far.
+    pub fn available_samples(&self) -> usize {
+        let frames = self.samples.lock().len() / self.channels.max(1) as usize;
+        (frames as u64 * TARGET_SAMPLE_RATE as u64 / self.sample_rate as u64) as usize
+    }
+
+    /// Captured audio from `start` (in 16 kHz samples) onwards, converted to 16 kHz mono.
+    pub fn pcm_from(&self, start: usize) -> Vec<f32> {
+        let channels = self.channels.max(1) as usize;
+        let start_frame =
+            (start as u64 * self.sample_rate as u64 / TARGET_SAMPLE_RATE as u64) as usize;
+        let raw = {
+            let guard = self.samples.lock();
+            let offset = (start_frame * channels).min(guard.len());
+            guard[offset..].to_vec()
+        };
+        downmix_and_resample(&raw, self.channels, self.sample_rate, TARGET_SAMPLE_RATE)
+    }
+}
+
 pub struct RecordingSession {
     pub id: Uuid,
     started: std::time::Instant,
     stop_tx: mpsc::Sender<()>,
     worker: Option<JoinHandle<Result<CapturedAudio>>>,
+    tap: AudioTap,
 }
 
 impl RecordingSession {
     pub fn begin() -> Result<Self> {
         let (stop_tx, stop_rx) = mpsc::channel::<()>();
-        let (init_tx, init_rx) = mpsc::channel::<Result<()>>();
+        let (init_tx, init_rx) = mpsc::channel::<Result<AudioTap>>();
 
         let worker = std::thread::spawn(move || {
             let host = cpal::default_host();
@@ -83,7 +118,11 @@ impl RecordingSession {
             };
 
             stream.play().context("failed to start microphone stream")?;
-            let _ = init_tx.send(Ok(()));
+            let _ = init_tx.send(Ok(AudioTap {
+                samples: Arc::clone(&samples),
+                sample_rate,
+                channels,
+            }));
 
             let _ = stop_rx.recv();
             drop(stream);
@@ -96,7 +135,7 @@ impl RecordingSession {
             })
         });
 
-        init_rx
+        let tap = init_rx
             .recv()
             .map_err(|_| anyhow!("failed to initialize recording thread"))??;
 
@@ -105,15 +144,20 @@ impl RecordingSession {
             started: std::time::Instant::now(),
             stop_tx,
             worker: Some(worker),
+            tap,
         })
     }
 
     pub fn elapsed_ms(&self) -> u128 {
         self.started.elapsed().as_millis()
     }
+
+    pub fn tap(&self) -> AudioTap {
+        self.tap.clone()
+    }
 }
 
-fn downmix_and_resample(input: &[f32], channels: u16, in_rate: u32, out_rate: u32) -> Vec<f32> {
+pub fn downmix_and_resample(input: &[f32], channels: u16, in_rate: u32, out_rate: u32) -> Vec<f32> {
     if input.is_empty() {
         return Vec::new();
     }
@@ -149,6 +193,29 @@ fn downmix_and_resample(input: &[f32], channels: u16, in_rate: u32, out_rate: u3
     out
 }
 
+fn frame_rms(frame: &[f32]) -> f32 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt()
+}
+
+/// Fraction of 20 ms frames whose energy is below the speech floor; 1.0 for empty input.
+pub fn silent_frame_ratio(pcm: &[f32]) -> f32 {
+    if pcm.is_empty() {
+        return 1.0;
+    }
+    let mut frames = 0usize;
+    let mut silent = 0usize;
+    for frame in pcm.chunks(ENERGY_FRAME_SAMPLES) {
+        frames += 1;
+        if frame_rms(frame) < SILENCE_RMS_FLOOR {
+            silent += 1;
+        }
+    }
+    silent as f32 / frames as f32
+}
+
 pub async fn finalize_capture(mut session: RecordingSession) -> Result<Vec<f32>> {
     let _ = session.stop_tx.send(());
     let worker = session
@@ -168,6 +235,6 @@ pub async fn finalize_capture(mut session: RecordingSession) -> Result<Vec<f32>>
         &captured.samples,
         captured.channels,
         captured.sample_rate,
-        16_000,
+        TARGET_SAMPLE_RATE,
     ))
 }
@@ -0,0 +1,355 @@
+use std::{fs, path::Path, time::Instant};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{
+    audio::{downmix_and_resample, TARGET_SAMPLE_RATE},
+    config::load_or_init_config,
+    context_cache::WhisperContextCache,
+    model::{list_models, model_path, supports_speaker_turns},
+    transcription::{run_chunked_inference, InferenceOptions},
+    types::{AppConfig, BenchmarkReport, ModelBenchmark},
+};
+
+/// Resource directory holding the reference clip and its transcript.
+pub const BENCHMARK_RESOURCE_DIR: &str = "resources/benchmark";
+const REFERENCE_CLIP: &str = "reference.wav";
+const REFERENCE_TRANSCRIPT: &str = "reference.txt";
+/// Dictation only feels immediate when inference takes well under the length of the audio.
+const MAX_REAL_TIME_FACTOR: f32 = 0.5;
+
+pub struct ReferenceClip {
+    pub pcm: Vec<f32>,
+    pub text: String,
+}
+
+/// Decodes 16-bit PCM or 32-bit float WAV data into 16 kHz mono samples.
+fn decode_wav(bytes: &[u8]) -> Result<Vec<f32>> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        bail!("reference clip is not a WAV file");
+    }
+    let u16_at = |data: &[u8], at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let u32_at = |data: &[u8], at: usize| {
+        u32::from_le_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]])
+    };
+
+    let mut format = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let size = u32_at(bytes, pos + 4) as usize;
+        let body = bytes
+            .get(pos + 8..pos + 8 + size)
+            .context("truncated WAV chunk")?;
+        match &bytes[pos..pos + 4] {
+            b"fmt " => {
+                if body.len() < 16 {
+                    bail!("invalid WAV format chunk");
+                }
+                format = Some((
+                    u16_at(body, 0),
+                    u16_at(body, 2),
+                    u32_at(body, 4),
+                    u16_at(body, 14),
+                ));
+            }
+            b"data" => {
+                let (encoding, channels, sample_rate, bits) =
+                    format.context("WAV data chunk comes before the format chunk")?;
+                let samples: Vec<f32> = match (encoding, bits) {
+                    (1, 16) => body
+                        .chunks_exact(2)
+                        .map(|sample| {
+                            f32::from(i16::from_le_bytes([sample[0], sample[1]])) / 32768.0
+                        })
+                        .collect(),
+                    (3, 32) => body
+                        .chunks_exact(4)
+                        .map(|sample| {
+                            f32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]])
+                        })
+                        .collect(),
+                    _ => bail!("unsupported WAV encoding {encoding} with {bits}-bit samples"),
+                };
+                return Ok(downmix_and_resample(
+                    &samples,
+                    channels,
+                    sample_rate,
+                    TARGET_SAMPLE_RATE,
+                ));
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even length.
+        pos += 8 + size + size % 2;
+    }
+    bail!("WAV file has no data chunk")
+}
+
+pub fn load_reference(clip: &Path, transcript: &Path) -> Result<ReferenceClip> {
+    let bytes = fs::read(clip)
+        .with_context(|| format!("failed to read reference clip {}", clip.display()))?;
+    let text = fs::read_to_string(transcript).with_context(|| {
+        format!(
+            "failed to read reference transcript {}",
+            transcript.display()
+        )
+    })?;
+    Ok(ReferenceClip {
+        pcm: decode_wav(&bytes)?,
+        text: text.trim().to_string(),
+    })
+}
+
+/// The bundled clip and transcript inside `dir`.
+pub fn load_bundled_reference(dir: &Path) -> Result<ReferenceClip> {
+    load_reference(&dir.join(REFERENCE_CLIP), &dir.join(REFERENCE_TRANSCRIPT))
+}
+
+fn normalize_words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '\'' {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Word-level edit distance divided by the number of reference words, ignoring case and
+/// punctuation.
+pub fn word_error_rate(reference: &str, hypothesis: &str) -> f32 {
+    let reference = normalize_words(reference);
+    let hypothesis = normalize_words(hypothesis);
+    if reference.is_empty() {
+        return if hypothesis.is_empty() { 0.0 } else { 1.0 };
+    }
+    let mut previous: Vec<usize> = (0..=hypothesis.len()).collect();
+    for (i, expected) in reference.iter().enumerate() {
+        let mut current = vec![i + 1; hypothesis.len() + 1];
+        for (j, word) in hypothesis.iter().enumerate() {
+            let substitution = previous[j] + usize::from(expected != word);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[hypothesis.len()] as f32 / reference.len() as f32
+}
+
+fn benchmark_options(config: &AppConfig, model_id: &str) -> InferenceOptions {
+    // The reference clip is English; personal vocabulary and context would skew the comparison.
+    let mut options = InferenceOptions::from_config(config);
+    options.language = "en".to_string();
+    options.translate = false;
+    options.speaker_turns = supports_speaker_turns(model_id);
+    options.word_timestamps = false;
+    options.vocabulary.clear();
+    options.previous_text = None;
+    options
+}
+
+fn benchmark_model(
+    model_id: &str,
+    file_name: String,
+    reference: &ReferenceClip,
+    config: &AppConfig,
+) -> ModelBenchmark {
+    let mut result = ModelBenchmark {
+        model_id: model_id.to_string(),
+        file_name,
+        load_ms: None,
+        inference_ms: None,
+        real_time_factor: None,
+        word_error_rate: None,
+        transcript: None,
+        error: None,
+    };
+    // A private cache so the load is timed from disk even if the app already has the model.
+    let contexts = WhisperContextCache::new();
+    let loaded = Instant::now();
+    let ctx = match model_path(model_id).and_then(|path| contexts.get_or_load(model_id, &path)) {
+        Ok(ctx) => ctx,
+        Err(err) => {
+            result.error = Some(err.to_string());
+            return result;
+        }
+    };
+    result.load_ms = Some(loaded.elapsed().as_millis() as u64);
+
+    let started = Instant::now();
+    match run_chunked_inference(&reference.pcm, &ctx, &benchmark_options(config, model_id)) {
+        Ok(output) => {
+            let elapsed = started.elapsed();
+            let clip_secs = reference.pcm.len() as f32 / TARGET_SAMPLE_RATE as f32;
+            result.inference_ms = Some(elapsed.as_millis() as u64);
+            result.real_time_factor = Some(elapsed.as_secs_f32() / clip_secs.max(f32::EPSILON));
+            result.word_error_rate = Some(word_error_rate(&reference.text, &output.text));
+            result.transcript = Some(output.text.trim().to_string());
+        }
+        Err(err) => result.error = Some(err.to_string()),
+    }
+    result
+}
+
+/// Most accurate model among those fast enough for dictation, or the fastest one if none is.
+fn recommend(results: &[ModelBenchmark]) -> (Option<String>, String) {
+    let measured: Vec<(&ModelBenchmark, f32, f32)> = results
+        .iter()
+        .filter_map(|result| Some((result, result.real_time_factor?, result.word_error_rate?)))
+        .collect();
+    let fast_enough = measured
+        .iter()
+        .filter(|(_, rtf, _)| *rtf <= MAX_REAL_TIME_FACTOR)
+        .min_by(|a, b| a.2.total_cmp(&b.2).then(a.1.total_cmp(&b.1)));
+    if let Some((result, rtf, wer)) = fast_enough {
+        return (
+            Some(result.model_id.clone()),
+            format!(
+                "{} has the lowest word error rate ({:.1}%) of the models that transcribe at \
+                 least {:.0}x faster than real time here (RTF {:.2})",
+                result.model_id,
+                wer * 100.0,
+                1.0 / MAX_REAL_TIME_FACTOR,
+                rtf
+            ),
+        );
+    }
+    match measured.iter().min_by(|a, b| a.1.total_cmp(&b.1)) {
+        Some((result, rtf, _)) => (
+            Some(result.model_id.clone()),
+            format!(
+                "no installed model reached an RTF of {MAX_REAL_TIME_FACTOR:.1}; {} was the \
+                 fastest (RTF {rtf:.2}), consider a smaller or quantized model",
+                result.model_id
+            ),
+        ),
+        None => (None, "no installed model could be benchmarked".to_string()),
+    }
+}
+
+/// Runs every installed model with a known id over the reference clip, one after another.
+pub fn benchmark_installed(
+    reference: &ReferenceClip,
+    config: &AppConfig,
+) -> Result<BenchmarkReport> {
+    if reference.pcm.is_empty() {
+        bail!("reference clip is empty");
+    }
+    let results: Vec<ModelBenchmark> = list_models("")?
+        .into_iter()
+        .filter_map(|model| Some((model.model_id?, model.file_name)))
+        .map(|(model_id, file_name)| benchmark_model(&model_id, file_name, reference, config))
+        .collect();
+    let (recommended_model, recommendation_reason) = recommend(&results);
+    Ok(BenchmarkReport {
+        clip_duration_ms: reference.pcm.len() as u64 * 1000 / u64::from(TARGET_SAMPLE_RATE),
+        reference_text: reference.text.clone(),
+        results,
+        recommended_model,
+        recommendation_reason,
+    })
+}
+
+/// `openspeak benchmark [<clip.wav> <transcript.txt>]`; defaults to the reference clip bundled
+/// in `resource_dir`.
+pub fn run_cli(args: &[String], resource_dir: &Path) -> Result<()> {
+    let reference = match args {
+        [] => load_bundled_reference(&resource_dir.join(BENCHMARK_RESOURCE_DIR))?,
+        [clip, transcript] => load_reference(Path::new(clip), Path::new(transcript))?,
+        _ => bail!("usage: openspeak benchmark [<clip.wav> <transcript.txt>]"),
+    };
+    let config = load_or_init_config()?;
+    let report = benchmark_installed(&reference, &config)?;
+
+    println!(
+        "{:<24} {:>9} {:>9} {:>6} {:>7}",
+        "model", "load ms", "infer ms", "RTF", "WER"
+    );
+    for result in &report.results {
+        match (
+            result.load_ms,
+            result.inference_ms,
+            result.real_time_factor,
+            result.word_error_rate,
+        ) {
+            (Some(load), Some(inference), Some(rtf), Some(wer)) => println!(
+                "{:<24} {load:>9} {inference:>9} {rtf:>6.2} {:>6.1}%",
+                result.model_id,
+                wer * 100.0
+            ),
+            _ => println!(
+                "{:<24} failed: {}",
+                result.model_id,
+                result.error.as_deref().unwrap_or("unknown error")
+            ),
+        }
+    }
+    match &report.recommended_model {
+        Some(model) => println!("\nrecommended: {model} - {}", report.recommendation_reason),
+        None => println!("\n{}", report.recommendation_reason),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{
+        decode_wav, load_bundled_reference, recommend, word_error_rate, BENCHMARK_RESOURCE_DIR,
+    };
+    use crate::{audio::TARGET_SAMPLE_RATE, remote::encode_wav, types::ModelBenchmark};
+
+    #[test]
+    fn loads_the_bundled_reference_clip() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(BENCHMARK_RESOURCE_DIR);
+        let reference = load_bundled_reference(&dir).unwrap();
+        // The whisper.cpp sample is about 11 seconds long.
+        assert!(reference.pcm.len() > 10 * TARGET_SAMPLE_RATE as usize);
+        assert!(reference.text.starts_with("And so my fellow Americans"));
+    }
+
+    #[test]
+    fn scores_word_errors_and_decodes_wav() {
+        let reference = "And so, my fellow Americans: ask not what your country can do for you.";
+        assert_eq!(word_error_rate(reference, &reference.to_uppercase()), 0.0);
+        // One substitution and one deletion over fourteen words.
+        let wer = word_error_rate(
+            reference,
+            "and so my fellow americans ask not what our country can do for",
+        );
+        assert!((wer - 2.0 / 14.0).abs() < 1e-6);
+
+        let pcm = decode_wav(&encode_wav(&[0.0, 0.5, -0.5])).unwrap();
+        assert_eq!(pcm.len(), 3);
+        assert!((pcm[1] - 0.5).abs() < 1e-3 && (pcm[2] + 0.5).abs() < 1e-3);
+        assert!(decode_wav(b"not a wav file").is_err());
+    }
+
+    #[test]
+    fn recommends_most_accurate_model_that_is_fast_enough() {
+        let bench = |model_id: &str, rtf: f32, wer: f32| ModelBenchmark {
+            model_id: model_id.to_string(),
+            file_name: format!("ggml-{model_id}.bin"),
+            load_ms: Some(100),
+            inference_ms: Some(1000),
+            real_time_factor: Some(rtf),
+            word_error_rate: Some(wer),
+            transcript: None,
+            error: None,
+        };
+        let results = [
+            bench("base", 0.1, 0.12),
+            bench("small", 0.3, 0.05),
+            bench("large-v3", 1.4, 0.02),
+        ];
+        assert_eq!(recommend(&results).0.as_deref(), Some("small"));
+        assert_eq!(recommend(&results[2..]).0.as_deref(), Some("large-v3"));
+        assert_eq!(recommend(&[]).0, None);
+    }
+}
@@ -0,0 +1,264 @@
+use std::ops::Range;
+
+use crate::{audio::TARGET_SAMPLE_RATE, transcription::WhisperOutput, types::TranscriptSegment};
+
+const SAMPLES_PER_SEC: usize = TARGET_SAMPLE_RATE as usize;
+/// Whisper decodes 30 s windows; staying under that keeps each chunk in a single pass.
+const MAX_CHUNK_SAMPLES: usize = 28 * SAMPLES_PER_SEC;
+/// Earliest point at which a chunk may be cut when looking for a pause.
+const MIN_CHUNK_SAMPLES: usize = 15 * SAMPLES_PER_SEC;
+const OVERLAP_SAMPLES: usize = SAMPLES_PER_SEC;
+/// 20 ms frames used to find the quietest cut point.
+const CUT_FRAME_SAMPLES: usize = 320;
+/// Longest run of words that is considered duplicated across a chunk boundary.
+const MAX_OVERLAP_WORDS: usize = 12;
+
+fn frame_energy(frame: &[f32]) -> f32 {
+    frame.iter().map(|s| s * s).sum::<f32>() / frame.len().max(1) as f32
+}
+
+/// Quietest frame boundary in `range`, so cuts land in pauses rather than mid-word.
+fn quietest_cut(pcm: &[f32], range: Range<usize>) -> usize {
+    let mut best = range.end;
+    let mut best_energy = f32::MAX;
+    let mut pos = range.start;
+    while pos + CUT_FRAME_SAMPLES <= range.end {
+        let energy = frame_energy(&pcm[pos..pos + CUT_FRAME_SAMPLES]);
+        if energy < best_energy {
+            best_energy = energy;
+            best = pos + CUT_FRAME_SAMPLES / 2;
+        }
+        pos += CUT_FRAME_SAMPLES;
+    }
+    best
+}
+
+/// Splits a long capture into overlapping sample ranges cut at silence boundaries.
+/// Captures that fit in one whisper window come back as a single range.
+pub fn plan_chunks(pcm: &[f32]) -> Vec<Range<usize>> {
+    if pcm.len() <= MAX_CHUNK_SAMPLES {
+        return vec![0..pcm.len()];
+    }
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    loop {
+        if pcm.len() - start <= MAX_CHUNK_SAMPLES {
+            chunks.push(start..pcm.len());
+            break;
+        }
+        let cut = quietest_cut(pcm, start + MIN_CHUNK_SAMPLES..start + MAX_CHUNK_SAMPLES);
+        chunks.push(start..cut);
+        start = cut - OVERLAP_SAMPLES;
+    }
+    chunks
+}
+
+pub fn normalize_word(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Number of leading words of `next` that repeat the trailing words of `previous`.
+fn overlapping_word_count(previous: &str, next: &str) -> usize {
+    let prev: Vec<String> = previous.split_whitespace().map(normalize_word).collect();
+    let next: Vec<String> = next.split_whitespace().map(normalize_word).collect();
+    let max = MAX_OVERLAP_WORDS.min(prev.len()).min(next.len());
+    (1..=max)
+        .rev()
+        .find(|&k| prev[prev.len() - k..] == next[..k])
+        .unwrap_or(0)
+}
+
+/// Appends `next` to `previous`, dropping words that were transcribed twice in the overlap.
+pub fn merge_overlapping_text(previous: &str, next: &str) -> String {
+    let skip = overlapping_word_count(previous, next);
+    let rest: Vec<&str> = next.split_whitespace().skip(skip).collect();
+    match (previous.is_empty(), rest.is_empty()) {
+        (_, true) => previous.to_string(),
+        (true, false) => rest.join(" "),
+        (false, false) => format!("{previous} {}", rest.join(" ")),
+    }
+}
+
+/// Drops the part of a segment that starts before `committed_end_ms`, which the previous chunk
+/// already transcribed. Word timings decide when present; otherwise the leading words that
+/// repeat the end of `previous` are removed. Returns false when nothing new is left.
+fn trim_seam(segment: &mut TranscriptSegment, committed_end_ms: u64, previous: &str) -> bool {
+    match segment.words.as_mut() {
+        Some(words) => {
+            words.retain(|word| (word.start_ms + word.end_ms) / 2 >= committed_end_ms);
+            segment.text = words
+                .iter()
+                .map(|word| word.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+        None => {
+            let skip = overlapping_word_count(previous, &segment.text);
+            segment.text = segment
+                .text
+                .split_whitespace()
+                .skip(skip)
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+    }
+    segment.start_ms = committed_end_ms;
+    !segment.text.is_empty()
+}
+
+/// Combines per-chunk outputs into one transcript with timestamps relative to the full capture.
+pub fn merge_chunk_outputs(chunks: &[Range<usize>], outputs: Vec<WhisperOutput>) -> WhisperOutput {
+    let mut merged = WhisperOutput::empty();
+    let mut weighted_confidence = 0.0;
+    let mut total_words = 0usize;
+
+    for (chunk, output) in chunks.iter().zip(outputs) {
+        let offset_ms = (chunk.start * 1000 / SAMPLES_PER_SEC) as u64;
+        let committed_end_ms = merged.segments.last().map_or(0, |s| s.end_ms);
+
+        merged.text = merge_overlapping_text(&merged.text, &output.text);
+        for mut segment in output.segments {
+            segment.start_ms += offset_ms;
+            segment.end_ms += offset_ms;
+            if let Some(words) = segment.words.as_mut() {
+                for word in words {
+                    word.start_ms += offset_ms;
+                    word.end_ms += offset_ms;
+                }
+            }
+            // Segments entirely inside the overlap were already emitted by the previous chunk.
+            if segment.end_ms <= committed_end_ms {
+                continue;
+            }
+            if segment.start_ms < committed_end_ms {
+                let previous = merged.segments.last().map_or("", |s| s.text.as_str());
+                if !trim_seam(&mut segment, committed_end_ms, previous) {
+                    continue;
+                }
+            }
+            let words = segment.text.split_whitespace().count();
+            weighted_confidence += segment.confidence * words as f32;
+            total_words += words;
+            merged.segments.push(segment);
+        }
+        if merged.detected_language.is_none() {
+            merged.detected_language = output.detected_language;
+            merged.language_probability = output.language_probability;
+        }
+    }
+
+    merged.confidence = if total_words == 0 {
+        0.0
+    } else {
+        weighted_confidence / total_words as f32
+    };
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        merge_chunk_outputs, merge_overlapping_text, plan_chunks, MAX_CHUNK_SAMPLES,
+        SAMPLES_PER_SEC,
+    };
+    use crate::{
+        transcription::WhisperOutput,
+        types::{TranscriptSegment, TranscriptWord},
+    };
+
+    fn output(text: &str, start_ms: u64, end_ms: u64) -> WhisperOutput {
+        let mut output = WhisperOutput::empty();
+        output.text = text.to_string();
+        output.segments = vec![TranscriptSegment {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            words: None,
+            confidence: 0.9,
+            min_token_probability: 0.9,
+            silent_ratio: 0.0,
+            no_speech_probability: None,
+            speaker_turn_next: false,
+        }];
+        output
+    }
+
+    #[test]
+    fn short_capture_is_single_chunk() {
+        let pcm = vec![0.2; 10 * SAMPLES_PER_SEC];
+        assert_eq!(plan_chunks(&pcm), vec![0..pcm.len()]);
+    }
+
+    #[test]
+    fn long_capture_is_cut_in_pauses_with_overlap() {
+        let mut pcm = vec![0.3; 70 * SAMPLES_PER_SEC];
+        let pause = 20 * SAMPLES_PER_SEC;
+        pcm[pause..pause + SAMPLES_PER_SEC / 2].fill(0.0);
+
+        let chunks = plan_chunks(&pcm);
+        assert!(chunks.len() >= 3);
+        assert!(chunks[0].end >= pause && chunks[0].end <= pause + SAMPLES_PER_SEC / 2);
+        assert_eq!(chunks[1].start, chunks[0].end - SAMPLES_PER_SEC);
+        assert!(chunks.iter().all(|c| c.len() <= MAX_CHUNK_SAMPLES));
+        assert_eq!(chunks.last().unwrap().end, pcm.len());
+    }
+
+    #[test]
+    fn drops_words_repeated_in_overlap() {
+        let merged = merge_overlapping_text(
+            "we should ship the release on Friday.",
+            "on friday, after the final review",
+        );
+        assert_eq!(
+            merged,
+            "we should ship the release on Friday. after the final review"
+        );
+    }
+
+    #[test]
+    fn trims_segment_straddling_the_seam() {
+        let chunks = [
+            0..20 * SAMPLES_PER_SEC,
+            19 * SAMPLES_PER_SEC..40 * SAMPLES_PER_SEC,
+        ];
+        let first = output("we should ship the release on Friday.", 0, 20_000);
+        let second = output("on friday, after the final review", 0, 5_000);
+        let merged = merge_chunk_outputs(&chunks, vec![first.clone(), second.clone()]);
+        assert_eq!(merged.segments.len(), 2);
+        assert_eq!(merged.segments[1].text, "after the final review");
+        assert_eq!(merged.segments[1].start_ms, 20_000);
+
+        // With word timings the words spoken before the committed end are dropped.
+        let mut timed = second;
+        timed.segments[0].words = Some(
+            [
+                ("on", 0, 400),
+                ("friday,", 400, 900),
+                ("after", 1_100, 1_500),
+            ]
+            .into_iter()
+            .map(|(text, start_ms, end_ms)| TranscriptWord {
+                text: text.to_string(),
+                start_ms,
+                end_ms,
+                probability: 0.9,
+            })
+            .collect(),
+        );
+        let merged = merge_chunk_outputs(&chunks, vec![first, timed]);
+        assert_eq!(merged.segments[1].text, "after");
+    }
+
+    #[test]
+    fn keeps_text_without_overlap() {
+        assert_eq!(
+            merge_overlapping_text("first part", "second part"),
+            "first part second part"
+        );
+        assert_eq!(merge_overlapping_text("", "only text"), "only text");
+    }
+}
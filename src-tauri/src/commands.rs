@@ -1,18 +1,36 @@
-use std::time::Instant;
+use std::{path::PathBuf, time::Instant};
 
-use tauri::{AppHandle, State};
+use tauri::{path::BaseDirectory, AppHandle, Emitter, Manager, State};
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
 
 use crate::{
     app_state::AppState,
     audio::{finalize_capture, RecordingSession},
-    command_parser::apply_basic_commands,
+    benchmark::{benchmark_installed, load_bundled_reference, BENCHMARK_RESOURCE_DIR},
     config::save_config,
-    injector::deliver_text,
-    model::{download_model as download_model_file, is_model_installed, is_supported_model},
+    engine::is_supported_engine,
+    jobs::{emit_job, enqueue_job, PendingJob},
+    model::{
+        active_model, add_model_from_url, cancel_download, clean_stale_parts, delete_model_file,
+        download_client, download_model as download_model_file, import_model as import_model_file,
+        inspect_model as inspect_model_file, is_model_installed, is_supported_model,
+        is_supported_quantization, list_models as list_installed_models, model_catalog,
+        model_filename, model_id_for_file, resolve_model, verify_model as verify_model_file,
+        MODEL_DOWNLOAD_PROGRESS_EVENT,
+    },
     overlay::set_overlay_visible,
-    transcription::transcribe_locally,
-    types::{AppConfig, AppStatus, RecordingState, TranscriptionResult},
+    preflight::preflight_model,
+    prompt::normalize_vocabulary,
+    remote::is_valid_base_url,
+    streaming::spawn_partial_transcriber,
+    transcription::{
+        is_supported_language, is_supported_task, validate_decoding, MAX_CHUNK_WORKERS,
+    },
+    types::{
+        AppConfig, AppStatus, BenchmarkReport, CatalogModel, CustomModel, DecodingConfig,
+        DownloadConfig, InstalledModel, ModelPreflight, ModelVariant, ModelVerification,
+        OutputFilterConfig, PartialCleanup, RecordingState, RemoteEngineConfig, TranscriptionJob,
+    },
 };
 
 fn set_last_error(state: &AppState, message: Option<String>) {
@@ -54,7 +72,7 @@ pub fn get_status(state: State<'_, AppState>) -> Result<AppStatus, String> {
             s.status.accessibility_granted = crate::platform::macos::accessibility_granted();
         }
         let mut out = s.status.clone();
-        out.model_ready = is_model_installed(&s.config.model_default);
+        out.model_ready = is_model_installed(&active_model(&s.config));
         out
     });
     Ok(status)
@@ -65,22 +83,41 @@ pub fn get_config(state: State<'_, AppState>) -> Result<AppConfig, String> {
     Ok(state.with_lock(|s| s.config.clone()))
 }
 
-#[tauri::command]
-pub fn set_hotkey(
-    app: AppHandle,
-    state: State<'_, AppState>,
-    hotkey_spec: String,
-) -> Result<(), String> {
-    let parsed_hotkey: Shortcut = hotkey_spec
-        .parse()
-        .map_err(|e| format!("invalid hotkey format: {e}"))?;
+fn parse_hotkey(spec: &str) -> Result<Shortcut, String> {
+    spec.parse()
+        .map_err(|e| format!("invalid hotkey format: {e}"))
+}
 
+/// Replaces every registered shortcut with the toggle hotkey and, if set, the cancel hotkey.
+fn register_hotkeys(
+    app: &AppHandle,
+    toggle: Shortcut,
+    cancel: Option<Shortcut>,
+) -> Result<(), String> {
     app.global_shortcut()
         .unregister_all()
         .map_err(|e| e.to_string())?;
     app.global_shortcut()
-        .register(parsed_hotkey)
+        .register(toggle)
         .map_err(|e| e.to_string())?;
+    if let Some(cancel) = cancel {
+        app.global_shortcut()
+            .register(cancel)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_hotkey(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    hotkey_spec: String,
+) -> Result<(), String> {
+    let parsed_hotkey = parse_hotkey(&hotkey_spec)?;
+    let cancel_hotkey = state.with_lock(|s| s.config.cancel_hotkey.clone());
+    let parsed_cancel = parse_hotkey(&cancel_hotkey).ok();
+    register_hotkeys(&app, parsed_hotkey, parsed_cancel)?;
 
     let result = state.with_lock(|s| {
         s.config.hotkey = hotkey_spec;
@@ -94,6 +131,30 @@ pub fn set_hotkey(
     result
 }
 
+#[tauri::command]
+pub fn set_cancel_hotkey(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    hotkey_spec: String,
+) -> Result<(), String> {
+    let hotkey_spec = hotkey_spec.trim().to_string();
+    let parsed_cancel = if hotkey_spec.is_empty() {
+        None
+    } else {
+        Some(parse_hotkey(&hotkey_spec)?)
+    };
+    let toggle_hotkey = state.with_lock(|s| s.config.hotkey.clone());
+    if parsed_cancel.is_some() && hotkey_spec.eq_ignore_ascii_case(&toggle_hotkey) {
+        return Err("cancel hotkey must differ from the dictation hotkey".to_string());
+    }
+    register_hotkeys(&app, parse_hotkey(&toggle_hotkey)?, parsed_cancel)?;
+
+    state.with_lock(|s| {
+        s.config.cancel_hotkey = hotkey_spec;
+        save_config(&s.config).map_err(|e| e.to_string())
+    })
+}
+
 #[tauri::command]
 pub fn reset_permissions(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
     #[cfg(target_os = "macos")]
@@ -153,16 +214,279 @@ pub fn enable_permissions(state: State<'_, AppState>) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn set_model(state: State<'_, AppState>, model_id: String) -> Result<(), String> {
+pub fn set_model(state: State<'_, AppState>, model_id: String) -> Result<ModelPreflight, String> {
     if !is_supported_model(&model_id) {
         return Err("invalid model id".to_string());
     }
+    let active = requested_model(&state, &model_id)?;
+    let preflight = checked_preflight(&active)?;
+    state.contexts.retain_only(&active);
     state.with_lock(|s| {
         s.config.model_default = model_id;
+        s.config.model_selected_by_user = true;
+        save_config(&s.config).map_err(|e| e.to_string())
+    })?;
+    Ok(preflight)
+}
+
+/// Preflight that turns an error-level issue into the command error.
+fn checked_preflight(model_id: &str) -> Result<ModelPreflight, String> {
+    let preflight = preflight_model(model_id).map_err(|e| e.to_string())?;
+    if let Some(issue) = preflight.blocking_issue() {
+        return Err(issue.message.clone());
+    }
+    Ok(preflight)
+}
+
+#[tauri::command]
+pub fn check_model(state: State<'_, AppState>, model_id: String) -> Result<ModelPreflight, String> {
+    let model_id = requested_model(&state, &model_id)?;
+    preflight_model(&model_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_model_variant(
+    state: State<'_, AppState>,
+    variant: ModelVariant,
+) -> Result<ModelPreflight, String> {
+    if variant
+        .quantization
+        .as_deref()
+        .is_some_and(|quantization| !is_supported_quantization(quantization))
+    {
+        return Err("invalid quantization".to_string());
+    }
+    let result = apply_model_variant(&state, variant);
+    if let Err(err) = &result {
+        set_last_error(&state, Some(err.clone()));
+    } else {
+        set_last_error(&state, None);
+    }
+    result
+}
+
+fn apply_model_variant(state: &AppState, variant: ModelVariant) -> Result<ModelPreflight, String> {
+    let active = state
+        .with_lock(|s| resolve_model(&s.config.model_default, &variant))
+        .map_err(|e| e.to_string())?;
+    let preflight = checked_preflight(&active)?;
+    state.contexts.retain_only(&active);
+    state.with_lock(|s| {
+        s.config.model_variant = variant;
+        s.config.model_selected_by_user = true;
+        save_config(&s.config).map_err(|e| e.to_string())
+    })?;
+    Ok(preflight)
+}
+
+#[tauri::command]
+pub fn get_model_catalog() -> Result<Vec<CatalogModel>, String> {
+    Ok(model_catalog())
+}
+
+/// Sizes are resolved through the configured variant; catalog and custom ids are used as is.
+fn requested_model(state: &AppState, model_id: &str) -> Result<String, String> {
+    state
+        .with_lock(|s| resolve_model(model_id, &s.config.model_variant))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_model_unload_timeout(
+    state: State<'_, AppState>,
+    timeout_secs: Option<u64>,
+) -> Result<(), String> {
+    state.with_lock(|s| {
+        s.config.model_unload_after_secs = timeout_secs.filter(|secs| *secs > 0);
+        save_config(&s.config).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+pub fn set_word_timestamps(state: State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    state.with_lock(|s| {
+        s.config.word_timestamps = enabled;
+        save_config(&s.config).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+pub fn set_streaming_preview(state: State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    state.with_lock(|s| {
+        s.config.streaming_preview = enabled;
+        save_config(&s.config).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+pub fn set_engine(state: State<'_, AppState>, engine: String) -> Result<(), String> {
+    if !is_supported_engine(&engine) {
+        return Err("invalid transcription engine".to_string());
+    }
+    state.with_lock(|s| {
+        s.config.engine = engine;
+        save_config(&s.config).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+pub fn set_remote_engine(
+    state: State<'_, AppState>,
+    remote: RemoteEngineConfig,
+) -> Result<(), String> {
+    if !is_valid_base_url(&remote.base_url) {
+        return Err("remote base URL must start with http:// or https://".to_string());
+    }
+    if remote.timeout_secs == 0 {
+        return Err("remote timeout must be at least one second".to_string());
+    }
+    let remote = RemoteEngineConfig {
+        base_url: remote.base_url.trim().to_string(),
+        ..remote
+    };
+    state.with_lock(|s| {
+        s.config.remote = remote;
+        save_config(&s.config).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+pub fn set_download_config(
+    state: State<'_, AppState>,
+    download: DownloadConfig,
+) -> Result<(), String> {
+    let download = DownloadConfig {
+        mirror_base_url: download.mirror_base_url.trim().to_string(),
+        url_overrides: download
+            .url_overrides
+            .into_iter()
+            .map(|(model_id, url)| (model_id, url.trim().to_string()))
+            .collect(),
+        proxy_url: download.proxy_url.trim().to_string(),
+        ca_cert_paths: download
+            .ca_cert_paths
+            .into_iter()
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty())
+            .collect(),
+    };
+    if !download.mirror_base_url.is_empty() && !is_valid_base_url(&download.mirror_base_url) {
+        return Err("mirror URL must start with http:// or https://".to_string());
+    }
+    if let Some((model_id, _)) = download
+        .url_overrides
+        .iter()
+        .find(|(_, url)| !is_valid_base_url(url))
+    {
+        return Err(format!(
+            "download URL for {model_id} must start with http:// or https://"
+        ));
+    }
+    // Building the client checks the proxy URL and loads every certificate file.
+    download_client(&download).map_err(|e| e.to_string())?;
+    let result = state.with_lock(|s| {
+        s.config.download = download;
+        save_config(&s.config).map_err(|e| e.to_string())
+    });
+    if let Err(err) = &result {
+        set_last_error(&state, Some(err.clone()));
+    } else {
+        set_last_error(&state, None);
+    }
+    result
+}
+
+#[tauri::command]
+pub fn set_chunk_workers(state: State<'_, AppState>, workers: usize) -> Result<(), String> {
+    if !(1..=MAX_CHUNK_WORKERS).contains(&workers) {
+        return Err(format!(
+            "chunk workers must be between 1 and {MAX_CHUNK_WORKERS}"
+        ));
+    }
+    state.with_lock(|s| {
+        s.config.chunk_workers = workers;
+        save_config(&s.config).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+pub fn set_output_filter(
+    state: State<'_, AppState>,
+    filter: OutputFilterConfig,
+) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&filter.no_speech_threshold) {
+        return Err("no-speech threshold must be between 0 and 1".to_string());
+    }
+    state.with_lock(|s| {
+        s.config.output_filter = filter;
+        save_config(&s.config).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+pub fn set_decoding(state: State<'_, AppState>, decoding: DecodingConfig) -> Result<(), String> {
+    validate_decoding(&decoding).map_err(|e| e.to_string())?;
+    state.with_lock(|s| {
+        s.config.decoding = decoding;
+        save_config(&s.config).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+pub fn set_vocabulary(state: State<'_, AppState>, terms: Vec<String>) -> Result<(), String> {
+    let terms = normalize_vocabulary(&terms);
+    state.with_lock(|s| {
+        s.config.vocabulary = terms;
+        save_config(&s.config).map_err(|e| e.to_string())
+    })
+}
+
+#[tauri::command]
+pub fn set_task(state: State<'_, AppState>, task: String) -> Result<(), String> {
+    if !is_supported_task(&task) {
+        return Err("invalid task".to_string());
+    }
+    let result = state.with_lock(|s| {
+        s.config.task = task;
+        save_config(&s.config).map_err(|e| e.to_string())
+    });
+    if let Err(err) = &result {
+        set_last_error(&state, Some(err.clone()));
+    } else {
+        set_last_error(&state, None);
+    }
+    result
+}
+
+#[tauri::command]
+pub fn set_carry_over_context(state: State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    state.with_lock(|s| {
+        s.config.carry_over_context = enabled;
+        if !enabled {
+            s.last_transcript = None;
+        }
         save_config(&s.config).map_err(|e| e.to_string())
     })
 }
 
+#[tauri::command]
+pub fn set_language(state: State<'_, AppState>, language: String) -> Result<(), String> {
+    let language = language.trim().to_lowercase();
+    if !is_supported_language(&language) {
+        return Err(format!("unsupported language: {language}"));
+    }
+    let result = state.with_lock(|s| {
+        s.config.language = language;
+        save_config(&s.config).map_err(|e| e.to_string())
+    });
+    if let Err(err) = &result {
+        set_last_error(&state, Some(err.clone()));
+    } else {
+        set_last_error(&state, None);
+    }
+    result
+}
+
 #[tauri::command]
 pub fn set_paste_mode(state: State<'_, AppState>, paste_mode: String) -> Result<(), String> {
     if !matches!(paste_mode.as_str(), "clipboard" | "auto-paste") {
@@ -181,8 +505,158 @@ pub fn set_paste_mode(state: State<'_, AppState>, paste_mode: String) -> Result<
 }
 
 #[tauri::command]
-pub async fn download_model(model_id: String) -> Result<String, String> {
-    download_model_file(&model_id)
+pub async fn download_model(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    model_id: String,
+) -> Result<String, String> {
+    let model_id = requested_model(&state, &model_id)?;
+    checked_preflight(&model_id)?;
+    let download = state.with_lock(|s| s.config.download.clone());
+    download_model_file(&model_id, &download, move |progress| {
+        let _ = app.emit(MODEL_DOWNLOAD_PROGRESS_EVENT, progress);
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn cancel_model_download(state: State<'_, AppState>, model_id: String) -> Result<(), String> {
+    let model_id = requested_model(&state, &model_id)?;
+    if cancel_download(&model_id) {
+        Ok(())
+    } else {
+        Err("no download in progress for this model".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn benchmark_models(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<BenchmarkReport, String> {
+    let (busy, config) = state.with_lock(|s| {
+        (
+            s.active_session.is_some() || s.jobs.is_busy(),
+            s.config.clone(),
+        )
+    });
+    if busy {
+        return Err("finish the current dictation before running the benchmark".to_string());
+    }
+    let dir = app
+        .path()
+        .resolve(BENCHMARK_RESOURCE_DIR, BaseDirectory::Resource)
+        .map_err(|e| e.to_string())?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let reference = load_bundled_reference(&dir)?;
+        benchmark_installed(&reference, &config)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_models(state: State<'_, AppState>) -> Result<Vec<InstalledModel>, String> {
+    let active = state.with_lock(|s| active_model(&s.config));
+    list_installed_models(&active).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn inspect_model(
+    state: State<'_, AppState>,
+    file_name: String,
+) -> Result<InstalledModel, String> {
+    let active = state.with_lock(|s| active_model(&s.config));
+    inspect_model_file(&file_name, &active)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Deletes a model file. The active model can only go if `switch_to` names another model,
+/// which becomes the new default once the file is gone; a failed delete changes nothing.
+#[tauri::command]
+pub fn delete_model(
+    state: State<'_, AppState>,
+    file_name: String,
+    switch_to: Option<String>,
+) -> Result<(), String> {
+    let active = state.with_lock(|s| active_model(&s.config));
+    let mut switch = None;
+    if model_filename(&active).as_deref() == Some(file_name.as_str()) {
+        let next = switch_to.ok_or_else(|| {
+            "cannot delete the active model without switching to another one".to_string()
+        })?;
+        if !is_supported_model(&next) {
+            return Err("invalid model id".to_string());
+        }
+        let next_active = requested_model(&state, &next)?;
+        if next_active == active {
+            return Err(
+                "cannot delete the active model without switching to another one".to_string(),
+            );
+        }
+        switch = Some((next, next_active));
+    }
+    if let Some(model_id) = model_id_for_file(&file_name) {
+        state.contexts.evict(&model_id);
+    }
+    delete_model_file(&file_name).map_err(|e| e.to_string())?;
+
+    if let Some((next, next_active)) = switch {
+        state.contexts.retain_only(&next_active);
+        state.with_lock(|s| {
+            s.config.model_default = next;
+            s.config.model_selected_by_user = true;
+            save_config(&s.config).map_err(|e| e.to_string())
+        })?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn import_model(
+    path: String,
+    model_id: Option<String>,
+    link: bool,
+) -> Result<CustomModel, String> {
+    import_model_file(PathBuf::from(path), model_id, link)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_model_url(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    model_id: String,
+    url: String,
+    sha256: Option<String>,
+) -> Result<CustomModel, String> {
+    let download = state.with_lock(|s| s.config.download.clone());
+    add_model_from_url(&model_id, &url, sha256, &download, move |progress| {
+        let _ = app.emit(MODEL_DOWNLOAD_PROGRESS_EVENT, progress);
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn clean_partial_downloads() -> Result<PartialCleanup, String> {
+    clean_stale_parts().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn verify_model(
+    state: State<'_, AppState>,
+    model_id: String,
+) -> Result<ModelVerification, String> {
+    if !is_supported_model(&model_id) {
+        return Err("unsupported model".to_string());
+    }
+    let model_id = requested_model(&state, &model_id)?;
+    verify_model_file(&model_id)
         .await
         .map_err(|e| e.to_string())
 }
@@ -195,89 +669,134 @@ pub fn start_recording_internal(app: &AppHandle, state: &AppState) -> Result<Str
             return Err("recording session already active".to_string());
         }
         let session = RecordingSession::begin().map_err(|e| e.to_string())?;
+        let preview = s
+            .config
+            .streaming_preview
+            .then(|| (session.id, session.tap()));
         let id = session.id.to_string();
         s.active_session = Some(session);
         s.status.recording_state = RecordingState::Recording;
-        Ok(id)
+        Ok((id, preview))
     });
-    if result.is_ok() {
-        set_overlay_visible(app, true);
+    let (id, preview) = result?;
+    if let Some((session_id, tap)) = preview {
+        spawn_partial_transcriber(app.clone(), session_id, tap);
     }
-    result
+    set_overlay_visible(app, true);
+    Ok(id)
 }
 
 pub async fn stop_recording_internal(
     app: &AppHandle,
     state: &AppState,
     session_id: String,
-) -> Result<TranscriptionResult, String> {
-    let maybe_session = state.with_lock(|s| {
-        let taken = s.active_session.take();
-        if taken.is_some() {
-            s.status.recording_state = RecordingState::Transcribing;
-        }
-        taken
-    });
+) -> Result<TranscriptionJob, String> {
+    let maybe_session = state.with_lock(|s| s.active_session.take());
     let session = maybe_session.ok_or_else(|| "no active recording session".to_string())?;
     if session.id.to_string() != session_id {
-        state.with_lock(|s| {
-            s.status.recording_state = RecordingState::Idle;
-        });
-        set_overlay_visible(app, false);
+        finish_without_job(app, state);
         return Err("session id mismatch".to_string());
     }
 
-    let result = async {
-        let start = Instant::now();
-        let session_elapsed_ms = session.elapsed_ms();
-        let pcm = finalize_capture(session).await.map_err(|e| e.to_string())?;
-        let (model_default, paste_mode) =
-            state.with_lock(|s| (s.config.model_default.clone(), s.config.paste_mode.clone()));
-        let whisper = transcribe_locally(pcm, &model_default)
-            .await
-            .map_err(|e| e.to_string())?;
-        let parsed = apply_basic_commands(&whisper.text);
-        let delivery =
-            deliver_text(&parsed.transformed_text, &paste_mode).map_err(|e| e.to_string())?;
-
-        Ok(TranscriptionResult {
-            raw_text: whisper.text,
-            transformed_text: parsed.transformed_text,
-            commands_applied: parsed.commands_applied,
-            latency_ms: start.elapsed().as_millis() + session_elapsed_ms,
-            confidence: whisper.confidence,
-            delivery,
-        })
-    }
-    .await;
+    let stopped_at = Instant::now();
+    let capture_ms = session.elapsed_ms();
+    let pcm = match finalize_capture(session).await {
+        Ok(pcm) => pcm,
+        Err(err) => {
+            finish_without_job(app, state);
+            return Err(err.to_string());
+        }
+    };
+    let config = state.with_lock(|s| s.config.clone());
+    let job = PendingJob::new(session_id, pcm, capture_ms, stopped_at, config);
+    // The worker owns delivery from here, so a new recording can start right away.
+    Ok(enqueue_job(app, state, job))
+}
 
-    state.with_lock(|s| {
-        s.status.recording_state = RecordingState::Idle;
-        s.status.last_error = result.as_ref().err().cloned();
+/// Returns to idle after a capture ended without producing a job, unless other jobs still run.
+fn finish_without_job(app: &AppHandle, state: &AppState) {
+    let idle = state.with_lock(|s| {
+        if s.jobs.is_busy() {
+            s.status.recording_state = RecordingState::Transcribing;
+            false
+        } else {
+            s.status.recording_state = RecordingState::Idle;
+            true
+        }
     });
-    set_overlay_visible(app, false);
-
-    result
+    if idle {
+        set_overlay_visible(app, false);
+    }
 }
 
 pub async fn toggle_recording_internal(
     app: &AppHandle,
     state: &AppState,
-) -> Result<Option<TranscriptionResult>, String> {
+) -> Result<Option<TranscriptionJob>, String> {
     let maybe_session_id = state.with_lock(|s| {
         s.active_session
             .as_ref()
             .map(|session| session.id.to_string())
     });
     if let Some(id) = maybe_session_id {
-        let result = stop_recording_internal(app, state, id).await?;
-        return Ok(Some(result));
+        let job = stop_recording_internal(app, state, id).await?;
+        return Ok(Some(job));
     }
 
     start_recording_internal(app, state)?;
     Ok(None)
 }
 
+/// Discards the active recording without transcribing it, or aborts the running transcription.
+pub fn cancel_recording_internal(app: &AppHandle, state: &AppState) -> Result<(), String> {
+    // Dropping the session closes its stop channel, which ends the capture thread.
+    let discarded = state.with_lock(|s| s.active_session.take().is_some());
+    if discarded {
+        finish_without_job(app, state);
+        return Ok(());
+    }
+    if state.with_lock(|s| s.jobs.cancel_running()) {
+        return Ok(());
+    }
+    Err("nothing to cancel".to_string())
+}
+
+#[tauri::command]
+pub fn cancel_transcription_job(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    job_id: String,
+) -> Result<(), String> {
+    let found = state.with_lock(|s| {
+        if let Some(job) = s.jobs.cancel(&job_id) {
+            return Ok(Some(job));
+        }
+        if s.jobs.snapshot().iter().any(|job| job.job_id == job_id) {
+            return Ok(None);
+        }
+        Err(format!("unknown transcription job: {job_id}"))
+    })?;
+    // A running job reports its own cancellation once the engine stops.
+    if let Some(job) = found {
+        emit_job(&app, &job);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_transcription_jobs(state: State<'_, AppState>) -> Result<Vec<TranscriptionJob>, String> {
+    Ok(state.with_lock(|s| s.jobs.snapshot()))
+}
+
+#[tauri::command]
+pub fn cancel_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let result = cancel_recording_internal(&app, &state);
+    if result.is_ok() {
+        set_last_error(&state, None);
+    }
+    result
+}
+
 #[tauri::command]
 pub fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Result<String, String> {
     let result = start_recording_internal(&app, &state);
@@ -294,7 +813,7 @@ pub async fn stop_recording(
     app: AppHandle,
     state: State<'_, AppState>,
     session_id: String,
-) -> Result<TranscriptionResult, String> {
+) -> Result<TranscriptionJob, String> {
     let result = stop_recording_internal(&app, &state, session_id).await;
     if let Err(err) = &result {
         set_last_error(&state, Some(err.clone()));
@@ -306,7 +825,7 @@ pub async fn stop_recording(
 pub async fn toggle_recording(
     app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<Option<TranscriptionResult>, String> {
+) -> Result<Option<TranscriptionJob>, String> {
     let result = toggle_recording_internal(&app, &state).await;
     if let Err(err) = &result {
         set_last_error(&state, Some(err.clone()));
@@ -2,7 +2,16 @@ use std::{fs, path::PathBuf};
 
 use anyhow::{Context, Result};
 
-use crate::types::{AppConfig, PrivacyConfig};
+use crate::{
+    engine::WHISPER_ENGINE,
+    hardware::{probe_hardware, recommend_model},
+    model::resolve_model,
+    transcription::TRANSCRIBE_TASK,
+    types::{
+        AppConfig, DecodingConfig, DownloadConfig, ModelVariant, OutputFilterConfig, PrivacyConfig,
+        RemoteEngineConfig,
+    },
+};
 
 const CONFIG_VERSION: u32 = 1;
 const APP_DATA_DIR: &str = "openspeak";
@@ -19,7 +28,11 @@ struct PersistedConfig {
 pub fn default_config() -> AppConfig {
     AppConfig {
         hotkey: "CommandOrControl+Shift+Space".to_string(),
+        cancel_hotkey: "CommandOrControl+Shift+Backspace".to_string(),
         model_default: "small".to_string(),
+        model_variant: ModelVariant::default(),
+        model_recommendation: None,
+        model_selected_by_user: false,
         command_mode: "basic".to_string(),
         paste_mode: "clipboard".to_string(),
         language: "en".to_string(),
@@ -27,6 +40,18 @@ pub fn default_config() -> AppConfig {
             telemetry_enabled: false,
             persist_audio_debug: false,
         },
+        model_unload_after_secs: None,
+        word_timestamps: false,
+        streaming_preview: false,
+        engine: WHISPER_ENGINE.to_string(),
+        remote: RemoteEngineConfig::default(),
+        download: DownloadConfig::default(),
+        chunk_workers: 1,
+        output_filter: OutputFilterConfig::default(),
+        decoding: DecodingConfig::default(),
+        vocabulary: Vec::new(),
+        carry_over_context: false,
+        task: TRANSCRIBE_TASK.to_string(),
     }
 }
 
@@ -48,10 +73,26 @@ fn config_path() -> Result<PathBuf> {
     Ok(dir)
 }
 
+/// Defaults with the model picked for this machine instead of the fixed `small`. The pick is
+/// stored as a catalog id so its quantization does not leak into the variant applied to
+/// whatever size the user chooses later.
+fn first_run_config() -> AppConfig {
+    let mut config = default_config();
+    let recommendation = recommend_model(&probe_hardware());
+    let variant = ModelVariant {
+        quantization: recommendation.quantization.clone(),
+        english_only: None,
+    };
+    config.model_default = resolve_model(&recommendation.model_id, &variant)
+        .unwrap_or_else(|_| recommendation.model_id.clone());
+    config.model_recommendation = Some(recommendation);
+    config
+}
+
 pub fn load_or_init_config() -> Result<AppConfig> {
     let path = config_path()?;
     if !path.exists() {
-        let config = default_config();
+        let config = first_run_config();
         save_config(&config)?;
         return Ok(config);
     }
@@ -61,7 +102,13 @@ pub fn load_or_init_config() -> Result<AppConfig> {
         serde_json::from_str(&raw).context("failed to parse config file JSON")?;
 
     if persisted.config_version != CONFIG_VERSION {
-        let cfg = default_config();
+        let mut cfg = first_run_config();
+        // Only the recommendation is redone; a model the user picked themselves is kept.
+        if persisted.config.model_selected_by_user {
+            cfg.model_default = persisted.config.model_default;
+            cfg.model_variant = persisted.config.model_variant;
+            cfg.model_selected_by_user = true;
+        }
         save_config(&cfg)?;
         return Ok(cfg);
     }
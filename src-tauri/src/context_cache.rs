@@ -0,0 +1,104 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use tauri::{AppHandle, Manager};
+use whisper_rs::{WhisperContext, WhisperContextParameters};
+
+use crate::app_state::AppState;
+
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+struct CachedContext {
+    path: PathBuf,
+    context: Arc<WhisperContext>,
+    last_used: Instant,
+}
+
+/// Keeps loaded whisper models in memory between dictations, keyed by model id.
+pub struct WhisperContextCache {
+    entries: Mutex<HashMap<String, CachedContext>>,
+    // Serializes model loads so two dictations never read the same file from disk at once.
+    load_lock: Mutex<()>,
+}
+
+impl WhisperContextCache {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            load_lock: Mutex::new(()),
+        }
+    }
+
+    fn cached(&self, model_id: &str, path: &Path) -> Option<Arc<WhisperContext>> {
+        let mut entries = self.entries.lock();
+        let entry = entries.get_mut(model_id)?;
+        if entry.path != path {
+            return None;
+        }
+        entry.last_used = Instant::now();
+        Some(Arc::clone(&entry.context))
+    }
+
+    pub fn get_or_load(&self, model_id: &str, path: &Path) -> Result<Arc<WhisperContext>> {
+        if let Some(context) = self.cached(model_id, path) {
+            return Ok(context);
+        }
+
+        let _loading = self.load_lock.lock();
+        if let Some(context) = self.cached(model_id, path) {
+            return Ok(context);
+        }
+
+        let path_str = path
+            .to_str()
+            .context("invalid model path for whisper runtime")?;
+        let context = Arc::new(
+            WhisperContext::new_with_params(path_str, WhisperContextParameters::default())
+                .context("failed to initialize whisper context")?,
+        );
+        self.entries.lock().insert(
+            model_id.to_string(),
+            CachedContext {
+                path: path.to_path_buf(),
+                context: Arc::clone(&context),
+                last_used: Instant::now(),
+            },
+        );
+        Ok(context)
+    }
+
+    pub fn evict(&self, model_id: &str) {
+        self.entries.lock().remove(model_id);
+    }
+
+    /// Drops every cached model except `model_id`, used when the active model changes.
+    pub fn retain_only(&self, model_id: &str) {
+        self.entries.lock().retain(|id, _| id == model_id);
+    }
+
+    pub fn unload_idle(&self, idle_for: Duration) {
+        self.entries
+            .lock()
+            .retain(|_, entry| entry.last_used.elapsed() < idle_for);
+    }
+}
+
+/// Periodically releases cached models that have not been used within the configured timeout.
+pub fn spawn_idle_unloader(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(IDLE_CHECK_INTERVAL).await;
+            let state = app.state::<AppState>();
+            let timeout = state.with_lock(|s| s.config.model_unload_after_secs);
+            if let Some(secs) = timeout.filter(|secs| *secs > 0) {
+                state.contexts.unload_idle(Duration::from_secs(secs));
+            }
+        }
+    });
+}
@@ -0,0 +1,151 @@
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use anyhow::Result;
+
+use crate::{
+    audio::TARGET_SAMPLE_RATE,
+    context_cache::WhisperContextCache,
+    model::active_model,
+    remote::transcribe_remote,
+    transcription::{transcribe_locally, InferenceOptions, TranscriptionCancelled, WhisperOutput},
+    types::{AppConfig, DownloadConfig, RemoteEngineConfig, TranscriptSegment},
+};
+
+pub const WHISPER_ENGINE: &str = "whisper";
+pub const REMOTE_ENGINE: &str = "remote";
+pub const MOCK_ENGINE: &str = "mock";
+const MOCK_TRANSCRIPT: &str = "this is a mock transcription period";
+
+pub type EngineFuture<'a> = Pin<Box<dyn Future<Output = Result<WhisperOutput>> + Send + 'a>>;
+
+/// Turns captured 16 kHz mono audio into text.
+pub trait TranscriptionEngine: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn transcribe<'a>(&'a self, pcm: Vec<f32>, options: &'a InferenceOptions) -> EngineFuture<'a>;
+}
+
+pub fn is_supported_engine(engine_id: &str) -> bool {
+    matches!(engine_id, WHISPER_ENGINE | REMOTE_ENGINE | MOCK_ENGINE)
+}
+
+/// Builds the engine selected in the config, falling back to local whisper for unknown ids.
+pub fn engine_for_config<'a>(
+    config: &AppConfig,
+    contexts: &'a Arc<WhisperContextCache>,
+) -> Box<dyn TranscriptionEngine + 'a> {
+    let local = WhisperEngine {
+        model: active_model(config),
+        download: config.download.clone(),
+        contexts,
+    };
+    match config.engine.as_str() {
+        MOCK_ENGINE => Box::new(MockEngine::default()),
+        REMOTE_ENGINE => Box::new(RemoteEngine {
+            config: config.remote.clone(),
+            fallback: config.remote.fallback_to_local.then_some(local),
+        }),
+        _ => Box::new(local),
+    }
+}
+
+/// Local whisper.cpp inference using the shared context cache.
+pub struct WhisperEngine<'a> {
+    pub model: String,
+    pub download: DownloadConfig,
+    pub contexts: &'a Arc<WhisperContextCache>,
+}
+
+impl TranscriptionEngine for WhisperEngine<'_> {
+    fn name(&self) -> &'static str {
+        WHISPER_ENGINE
+    }
+
+    fn transcribe<'a>(&'a self, pcm: Vec<f32>, options: &'a InferenceOptions) -> EngineFuture<'a> {
+        Box::pin(transcribe_locally(
+            pcm,
+            &self.model,
+            &self.download,
+            options,
+            self.contexts,
+        ))
+    }
+}
+
+/// OpenAI-compatible HTTP server, optionally falling back to local whisper when it fails.
+pub struct RemoteEngine<'a> {
+    pub config: RemoteEngineConfig,
+    pub fallback: Option<WhisperEngine<'a>>,
+}
+
+impl TranscriptionEngine for RemoteEngine<'_> {
+    fn name(&self) -> &'static str {
+        REMOTE_ENGINE
+    }
+
+    fn transcribe<'a>(&'a self, pcm: Vec<f32>, options: &'a InferenceOptions) -> EngineFuture<'a> {
+        Box::pin(async move {
+            let remote_error = match transcribe_remote(&pcm, &self.config, options).await {
+                Ok(output) => return Ok(output),
+                Err(err) => err,
+            };
+            if options.is_cancelled() {
+                return Err(TranscriptionCancelled.into());
+            }
+            let Some(fallback) = &self.fallback else {
+                return Err(remote_error);
+            };
+            eprintln!(
+                "{} transcription failed (falling back to {}): {remote_error:#}",
+                self.name(),
+                fallback.name()
+            );
+            fallback.transcribe(pcm, options).await
+        })
+    }
+}
+
+/// Deterministic engine for tests and CI: returns a fixed transcript spanning the whole capture.
+pub struct MockEngine {
+    pub transcript: String,
+}
+
+impl Default for MockEngine {
+    fn default() -> Self {
+        Self {
+            transcript: MOCK_TRANSCRIPT.to_string(),
+        }
+    }
+}
+
+impl TranscriptionEngine for MockEngine {
+    fn name(&self) -> &'static str {
+        MOCK_ENGINE
+    }
+
+    fn transcribe<'a>(&'a self, pcm: Vec<f32>, _options: &'a InferenceOptions) -> EngineFuture<'a> {
+        Box::pin(async move {
+            if pcm.is_empty() {
+                return Ok(WhisperOutput::empty());
+            }
+            let end_ms = pcm.len() as u64 * 1000 / TARGET_SAMPLE_RATE as u64;
+            Ok(WhisperOutput {
+                text: self.transcript.clone(),
+                confidence: 1.0,
+                segments: vec![TranscriptSegment {
+                    text: self.transcript.clone(),
+                    start_ms: 0,
+                    end_ms,
+                    words: None,
+                    confidence: 1.0,
+                    min_token_probability: 1.0,
+                    silent_ratio: 0.0,
+                    no_speech_probability: None,
+                    speaker_turn_next: false,
+                }],
+                detected_language: None,
+                language_probability: None,
+            })
+        })
+    }
+}
@@ -0,0 +1,388 @@
+use crate::{
+    transcription::WhisperOutput,
+    types::{OutputFilterConfig, TranscriptSegment},
+};
+
+/// Phrases whisper hallucinates from subtitle training data when it hears silence or noise.
+const STOCK_PHRASES: &[&str] = &[
+    "thanks for watching",
+    "thank you for watching",
+    "thank you so much for watching",
+    "thanks for watching and see you next time",
+    "please subscribe",
+    "please like and subscribe",
+    "dont forget to like and subscribe",
+    "subtitles by the amaraorg community",
+    "see you next time",
+];
+/// Words marking a bracketed span as a sound cue rather than speech, as in `[MUSIC PLAYING]`,
+/// `(laughs)`, `*clears throat*` or `[BLANK_AUDIO]`.
+const NON_SPEECH_WORDS: &[&str] = &[
+    "music",
+    "applause",
+    "clapping",
+    "laughter",
+    "laugh",
+    "laughs",
+    "laughing",
+    "chuckles",
+    "chuckling",
+    "sigh",
+    "sighs",
+    "sighing",
+    "cough",
+    "coughs",
+    "coughing",
+    "throat",
+    "inaudible",
+    "indistinct",
+    "silence",
+    "noise",
+    "static",
+    "beep",
+    "beeping",
+    "blank",
+];
+/// Longest bracketed span (in words) still treated as a sound cue.
+const MAX_ANNOTATION_WORDS: usize = 4;
+/// Longest phrase (in words) checked for runaway repetition.
+const MAX_REPEATED_PHRASE_WORDS: usize = 8;
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether the inside of a bracketed span is a short sound cue; a spoken parenthetical such as
+/// "(application programming interface)" is not.
+fn is_sound_cue(inner: &str) -> bool {
+    let normalized = normalize(&inner.replace('_', " "));
+    let words: Vec<&str> = normalized.split(' ').collect();
+    words.len() <= MAX_ANNOTATION_WORDS && words.iter().any(|word| NON_SPEECH_WORDS.contains(word))
+}
+
+/// Removes non-speech annotations such as `[Applause]`, `(music)`, `*sigh*` and `♪ ... ♪`.
+pub fn strip_annotations(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+        let closing = match c {
+            '[' => Some(']'),
+            '(' => Some(')'),
+            '*' | '♪' => Some(c),
+            _ => None,
+        };
+        // Only a closed span can be an annotation; a lone opener is part of what was said.
+        let span = closing.and_then(|end| after.find(end).map(|idx| (idx, idx + end.len_utf8())));
+        match span {
+            Some((inner_end, span_end)) if c == '♪' || is_sound_cue(&after[..inner_end]) => {
+                rest = &after[span_end..];
+            }
+            _ => {
+                out.push(c);
+                rest = after;
+            }
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Splits after sentence-ending punctuation, keeping the punctuation with its sentence.
+fn sentences(text: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    for (idx, c) in text.char_indices() {
+        if matches!(c, '.' | '!' | '?') {
+            let end = idx + c.len_utf8();
+            let at_boundary = text[end..].chars().next().is_none_or(char::is_whitespace);
+            if at_boundary {
+                out.push(text[start..end].trim());
+                start = end;
+            }
+        }
+    }
+    if start < text.len() {
+        out.push(text[start..].trim());
+    }
+    out.into_iter().filter(|s| !s.is_empty()).collect()
+}
+
+pub fn is_stock_phrase(sentence: &str) -> bool {
+    let normalized = normalize(sentence);
+    STOCK_PHRASES.contains(&normalized.as_str())
+}
+
+/// Drops whole sentences that are known subtitle hallucinations.
+pub fn drop_stock_phrases(text: &str) -> String {
+    sentences(text)
+        .into_iter()
+        .filter(|sentence| !is_stock_phrase(sentence))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Collapses a phrase repeated back to back more than `max_repeats` times into one occurrence.
+pub fn collapse_repetitions(text: &str, max_repeats: usize) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let keys: Vec<String> = words.iter().map(|w| normalize(w)).collect();
+    let mut out: Vec<&str> = Vec::with_capacity(words.len());
+    let mut i = 0;
+
+    while i < words.len() {
+        let mut collapsed = false;
+        for n in 1..=MAX_REPEATED_PHRASE_WORDS.min(words.len() - i) {
+            let phrase = &keys[i..i + n];
+            let mut repeats = 1;
+            while i + (repeats + 1) * n <= words.len()
+                && keys[i + repeats * n..i + (repeats + 1) * n] == *phrase
+            {
+                repeats += 1;
+            }
+            if repeats > max_repeats {
+                out.extend_from_slice(&words[i..i + n]);
+                i += repeats * n;
+                collapsed = true;
+                break;
+            }
+        }
+        if !collapsed {
+            out.push(words[i]);
+            i += 1;
+        }
+    }
+    out.join(" ")
+}
+
+pub fn filter_text(text: &str, config: &OutputFilterConfig) -> String {
+    let mut text = text.to_string();
+    if config.strip_annotations {
+        text = strip_annotations(&text);
+    }
+    if config.drop_stock_phrases {
+        text = drop_stock_phrases(&text);
+    }
+    if config.max_phrase_repeats > 0 {
+        text = collapse_repetitions(&text, config.max_phrase_repeats);
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether the engine is confident the segment is not speech. Segments without a model
+/// probability are always kept; quiet audio alone does not make a segment silent.
+fn is_no_speech(segment: &TranscriptSegment, config: &OutputFilterConfig) -> bool {
+    segment
+        .no_speech_probability
+        .is_some_and(|probability| probability >= config.no_speech_threshold)
+}
+
+/// Whether segments the engine marks as no speech cover most of the audio it reported on. The
+/// rest is then usually a hallucination over silence too, so the whole output is discarded.
+fn is_mostly_no_speech(segments: &[TranscriptSegment], config: &OutputFilterConfig) -> bool {
+    let mut reported_ms = 0;
+    let mut no_speech_ms = 0;
+    for segment in segments {
+        if segment.no_speech_probability.is_none() {
+            continue;
+        }
+        let duration = segment.end_ms.saturating_sub(segment.start_ms).max(1);
+        reported_ms += duration;
+        if is_no_speech(segment, config) {
+            no_speech_ms += duration;
+        }
+    }
+    reported_ms > 0 && no_speech_ms * 2 > reported_ms
+}
+
+/// Cleans an engine's output before it is parsed and delivered.
+pub fn filter_output(mut output: WhisperOutput, config: &OutputFilterConfig) -> WhisperOutput {
+    if !config.enabled {
+        return output;
+    }
+    if is_mostly_no_speech(&output.segments, config) {
+        output.text.clear();
+        output.segments.clear();
+        return output;
+    }
+
+    let drops_segments = output
+        .segments
+        .iter()
+        .any(|segment| is_no_speech(segment, config));
+    output.text = filter_text(&output.text, config);
+    let mut segments: Vec<TranscriptSegment> = Vec::with_capacity(output.segments.len());
+    for mut segment in output.segments {
+        segment.text = if is_no_speech(&segment, config) {
+            String::new()
+        } else {
+            filter_text(&segment.text, config)
+        };
+        if !segment.text.is_empty() {
+            segments.push(segment);
+        } else if segment.speaker_turn_next {
+            // Keep the boundary when the segment carrying it is filtered away.
+            if let Some(previous) = segments.last_mut() {
+                previous.speaker_turn_next = true;
+            }
+        }
+    }
+    if drops_segments {
+        // The full text still holds whatever the dropped segments decoded to.
+        let kept: Vec<&str> = segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .collect();
+        output.text = filter_text(&kept.join(" "), config);
+    }
+    output.segments = segments;
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collapse_repetitions, drop_stock_phrases, filter_output, strip_annotations};
+    use crate::{
+        transcription::WhisperOutput,
+        types::{OutputFilterConfig, TranscriptSegment},
+    };
+
+    fn segment(text: &str, no_speech_probability: f32) -> TranscriptSegment {
+        TranscriptSegment {
+            text: text.to_string(),
+            start_ms: 0,
+            end_ms: 1_000,
+            words: None,
+            confidence: 0.9,
+            min_token_probability: 0.9,
+            silent_ratio: 0.0,
+            no_speech_probability: Some(no_speech_probability),
+            speaker_turn_next: false,
+        }
+    }
+
+    #[test]
+    fn strips_bracketed_and_parenthesized_annotations() {
+        assert_eq!(
+            strip_annotations("[Applause] thank you all"),
+            "thank you all"
+        );
+        assert_eq!(strip_annotations("so (music) anyway"), "so anyway");
+        assert_eq!(strip_annotations("[BLANK_AUDIO]"), "");
+        assert_eq!(strip_annotations("[MUSIC PLAYING] ok"), "ok");
+    }
+
+    #[test]
+    fn keeps_spoken_parentheticals() {
+        assert_eq!(
+            strip_annotations("the API (application programming interface) is ready"),
+            "the API (application programming interface) is ready"
+        );
+        assert_eq!(strip_annotations("5 * 3 * 2"), "5 * 3 * 2");
+        assert_eq!(
+            strip_annotations("see [the appendix] (laughs)"),
+            "see [the appendix]"
+        );
+    }
+
+    #[test]
+    fn keeps_unterminated_openers() {
+        assert_eq!(strip_annotations("use a (b"), "use a (b");
+        assert_eq!(strip_annotations("*sigh* 5 * 3 [sic"), "5 * 3 [sic");
+    }
+
+    #[test]
+    fn strips_asterisk_and_music_note_annotations() {
+        assert_eq!(strip_annotations("*sigh* fine, let's go"), "fine, let's go");
+        assert_eq!(strip_annotations("♪ la la la ♪ next item"), "next item");
+    }
+
+    #[test]
+    fn drops_subtitle_stock_phrases() {
+        assert_eq!(drop_stock_phrases("Thanks for watching!"), "");
+        assert_eq!(
+            drop_stock_phrases("Send the report today. Thank you for watching."),
+            "Send the report today."
+        );
+        assert_eq!(
+            drop_stock_phrases("Thanks for watching the demo yesterday, Sam."),
+            "Thanks for watching the demo yesterday, Sam."
+        );
+    }
+
+    #[test]
+    fn collapses_runaway_repetitions() {
+        assert_eq!(
+            collapse_repetitions("I think I think I think I think I think we agree", 3),
+            "I think we agree"
+        );
+        assert_eq!(collapse_repetitions("no no no no no no", 3), "no");
+        assert_eq!(collapse_repetitions("very very good", 3), "very very good");
+    }
+
+    #[test]
+    fn drops_segments_with_high_no_speech_probability() {
+        let config = OutputFilterConfig::default();
+        let mut output = WhisperOutput::empty();
+        output.text = "you send it today".to_string();
+        output.segments = vec![segment("you", 0.97), segment("send it today", 0.1)];
+        let filtered = filter_output(output, &config);
+        assert_eq!(filtered.text, "send it today");
+        assert_eq!(filtered.segments.len(), 1);
+
+        // Without a model probability, a quiet segment is kept however silent it measured.
+        let mut quiet = segment("send it today", 0.0);
+        quiet.no_speech_probability = None;
+        quiet.silent_ratio = 0.98;
+        let mut output = WhisperOutput::empty();
+        output.text = "send it today".to_string();
+        output.segments = vec![quiet];
+        assert_eq!(filter_output(output, &config).text, "send it today");
+
+        let mut output = WhisperOutput::empty();
+        output.text = "(music) real words".to_string();
+        output.segments = vec![segment("(music) real words", 0.2)];
+        let filtered = filter_output(output, &config);
+        assert_eq!(filtered.text, "real words");
+        assert_eq!(filtered.segments[0].text, "real words");
+    }
+
+    #[test]
+    fn discards_output_that_is_mostly_no_speech() {
+        let mut output = WhisperOutput::empty();
+        output.text = "thank you bye".to_string();
+        output.segments = vec![
+            segment("thank you", 0.95),
+            segment("bye", 0.95),
+            segment("so", 0.4),
+        ];
+        let filtered = filter_output(output, &OutputFilterConfig::default());
+        assert!(filtered.text.is_empty());
+        assert!(filtered.segments.is_empty());
+    }
+
+    #[test]
+    fn keeps_speaker_turn_of_filtered_segment() {
+        let mut annotation = segment("[Laughter]", 0.1);
+        annotation.speaker_turn_next = true;
+        let mut output = WhisperOutput::empty();
+        output.text = "so that's the plan [Laughter] sounds good".to_string();
+        output.segments = vec![
+            segment("so that's the plan", 0.1),
+            annotation,
+            segment("sounds good", 0.1),
+        ];
+
+        let filtered = filter_output(output, &OutputFilterConfig::default());
+        assert_eq!(filtered.segments.len(), 2);
+        assert!(filtered.segments[0].speaker_turn_next);
+        assert!(!filtered.segments[1].speaker_turn_next);
+    }
+}
@@ -0,0 +1,92 @@
+use std::{fs, io::Read, path::Path};
+
+use anyhow::{bail, Context, Result};
+
+use crate::types::ModelHeader;
+
+/// "ggml" as a little-endian u32, the first word of every whisper.cpp model file.
+const GGML_MAGIC: u32 = 0x6767_6d6c;
+/// whisper.cpp treats vocabularies at least this large as multilingual.
+const MULTILINGUAL_VOCAB: i32 = 51_865;
+const HEADER_WORDS: usize = 12;
+
+/// Reads and sanity-checks the hyperparameters at the start of a ggml whisper model, so a
+/// wrong or truncated file is rejected before it is ever handed to whisper.cpp.
+pub fn read_header(path: &Path) -> Result<ModelHeader> {
+    let mut file = fs::File::open(path).context("failed to open model file")?;
+    let mut buf = [0u8; HEADER_WORDS * 4];
+    file.read_exact(&mut buf)
+        .context("model file is too small to be a ggml whisper model")?;
+    let word = |index: usize| {
+        let bytes: [u8; 4] = buf[index * 4..index * 4 + 4].try_into().unwrap_or_default();
+        i32::from_le_bytes(bytes)
+    };
+
+    if word(0) as u32 != GGML_MAGIC {
+        bail!("not a ggml whisper model (bad magic)");
+    }
+    let header = ModelHeader {
+        n_vocab: word(1),
+        n_audio_layer: word(5),
+        n_text_layer: word(9),
+        n_mels: word(10),
+        ftype: word(11),
+        multilingual: word(1) >= MULTILINGUAL_VOCAB,
+    };
+    if header.n_vocab <= 0
+        || !(1..=64).contains(&header.n_audio_layer)
+        || !(1..=64).contains(&header.n_text_layer)
+        || !matches!(header.n_mels, 80 | 128)
+    {
+        bail!("ggml model header has implausible hyperparameters");
+    }
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{read_header, GGML_MAGIC};
+
+    fn header_bytes(magic: u32, n_vocab: i32, n_mels: i32) -> Vec<u8> {
+        // magic, vocab, audio ctx/state/head/layer, text ctx/state/head/layer, mels, ftype
+        let words = [
+            magic as i32,
+            n_vocab,
+            1500,
+            384,
+            6,
+            4,
+            448,
+            384,
+            6,
+            4,
+            n_mels,
+            1,
+        ];
+        words.iter().flat_map(|word| word.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn accepts_ggml_header_and_rejects_other_files() {
+        let path = std::env::temp_dir().join(format!("openspeak-ggml-{}.bin", std::process::id()));
+
+        fs::write(&path, header_bytes(GGML_MAGIC, 51_865, 80)).unwrap();
+        let header = read_header(&path).unwrap();
+        assert!(header.multilingual);
+        assert_eq!(header.n_text_layer, 4);
+
+        fs::write(&path, header_bytes(GGML_MAGIC, 51_864, 80)).unwrap();
+        assert!(!read_header(&path).unwrap().multilingual);
+
+        fs::write(&path, header_bytes(0x1234_5678, 51_865, 80)).unwrap();
+        assert!(read_header(&path).is_err());
+        fs::write(&path, header_bytes(GGML_MAGIC, 51_865, 3)).unwrap();
+        assert!(read_header(&path).is_err());
+        fs::write(&path, b"ggml").unwrap();
+        assert!(read_header(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
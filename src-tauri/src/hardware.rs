@@ -0,0 +1,178 @@
+use sysinfo::System;
+
+use crate::{
+    model::{model_requirements, resolve_model},
+    types::{ModelRecommendation, ModelVariant},
+};
+
+const GB: u64 = 1_000_000_000;
+/// A recommended model may use at most this share of total memory, leaving room for the OS
+/// and whatever the user is dictating into.
+const MAX_MEMORY_SHARE: f64 = 0.25;
+
+pub struct HardwareProfile {
+    pub cpu_cores: usize,
+    pub simd_features: Vec<String>,
+    pub total_memory_bytes: u64,
+}
+
+impl HardwareProfile {
+    /// Whether whisper.cpp has vectorized kernels for this CPU: AVX2 with FMA on x86, NEON
+    /// with dot-product instructions on ARM.
+    fn fast_simd(&self) -> bool {
+        let has = |feature: &str| self.simd_features.iter().any(|f| f == feature);
+        (has("avx2") && has("fma")) || (has("neon") && has("dotprod"))
+    }
+}
+
+fn simd_features() -> Vec<String> {
+    #[allow(unused_mut)]
+    let mut features: Vec<&str> = Vec::new();
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        for (feature, detected) in [
+            ("sse4.1", std::arch::is_x86_feature_detected!("sse4.1")),
+            ("avx", std::arch::is_x86_feature_detected!("avx")),
+            ("avx2", std::arch::is_x86_feature_detected!("avx2")),
+            ("fma", std::arch::is_x86_feature_detected!("fma")),
+            ("f16c", std::arch::is_x86_feature_detected!("f16c")),
+            ("avx512f", std::arch::is_x86_feature_detected!("avx512f")),
+        ] {
+            if detected {
+                features.push(feature);
+            }
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        for (feature, detected) in [
+            ("neon", std::arch::is_aarch64_feature_detected!("neon")),
+            (
+                "dotprod",
+                std::arch::is_aarch64_feature_detected!("dotprod"),
+            ),
+            ("fp16", std::arch::is_aarch64_feature_detected!("fp16")),
+        ] {
+            if detected {
+                features.push(feature);
+            }
+        }
+    }
+    features.into_iter().map(str::to_string).collect()
+}
+
+pub fn probe_hardware() -> HardwareProfile {
+    let mut system = System::new();
+    system.refresh_memory();
+    HardwareProfile {
+        cpu_cores: std::thread::available_parallelism().map_or(1, |cores| cores.get()),
+        simd_features: simd_features(),
+        total_memory_bytes: system.total_memory(),
+    }
+}
+
+/// Picks a model size (and quantization for the large ones) that the CPU should transcribe
+/// faster than real time, then steps down while the catalog memory estimate would take too
+/// much of total memory.
+pub fn recommend_model(hardware: &HardwareProfile) -> ModelRecommendation {
+    let memory_gb = hardware.total_memory_bytes as f64 / GB as f64;
+    let summary = format!(
+        "{} cores, {:.0} GB RAM, {}",
+        hardware.cpu_cores,
+        memory_gb,
+        if hardware.simd_features.is_empty() {
+            "no SIMD extensions detected".to_string()
+        } else {
+            hardware.simd_features.join("/")
+        }
+    );
+
+    let (mut index, mut reason) = if hardware.cpu_cores < 2 {
+        (0, "a single core only keeps up with tiny")
+    } else if !hardware.fast_simd() || hardware.cpu_cores < 4 {
+        (
+            1,
+            "without fast SIMD kernels or four cores, small decodes slower than speech",
+        )
+    } else if hardware.cpu_cores >= 8 {
+        (
+            3,
+            "eight or more cores with fast SIMD run quantized turbo faster than real time",
+        )
+    } else {
+        (
+            2,
+            "a mid-range CPU with fast SIMD handles small comfortably",
+        )
+    };
+
+    // Ordered from lightest to heaviest.
+    let candidates = [
+        ("tiny", None),
+        ("base", None),
+        ("small", None),
+        ("turbo", Some("q5_0")),
+    ];
+    let fits = |(size, quantization): (&str, Option<&str>)| {
+        let variant = ModelVariant {
+            quantization: quantization.map(str::to_string),
+            english_only: None,
+        };
+        resolve_model(size, &variant)
+            .ok()
+            .and_then(|model_id| model_requirements(&model_id))
+            .is_none_or(|requirements| {
+                requirements.ram_bytes as f64
+                    <= hardware.total_memory_bytes as f64 * MAX_MEMORY_SHARE
+            })
+    };
+    while index > 0 && !fits(candidates[index]) {
+        index -= 1;
+        reason = "the faster hardware tier's model would use too much of the available memory";
+    }
+
+    let (model_id, quantization) = candidates[index];
+    ModelRecommendation {
+        model_id: model_id.to_string(),
+        quantization: quantization.map(str::to_string),
+        reason: format!("{model_id}: {reason} ({summary})"),
+        cpu_cores: hardware.cpu_cores,
+        simd_features: hardware.simd_features.clone(),
+        total_memory_bytes: hardware.total_memory_bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{recommend_model, HardwareProfile, GB};
+
+    fn machine(cpu_cores: usize, simd: &[&str], memory_gb: u64) -> HardwareProfile {
+        HardwareProfile {
+            cpu_cores,
+            simd_features: simd.iter().map(|feature| feature.to_string()).collect(),
+            total_memory_bytes: memory_gb * GB,
+        }
+    }
+
+    #[test]
+    fn scales_recommendation_with_hardware() {
+        let old_laptop = recommend_model(&machine(2, &["sse4.1", "avx"], 4));
+        assert_eq!(old_laptop.model_id, "base");
+        assert!(old_laptop.reason.contains("2 cores"));
+
+        let mid_range = recommend_model(&machine(4, &["avx", "avx2", "fma"], 8));
+        assert_eq!(mid_range.model_id, "small");
+        assert_eq!(mid_range.quantization, None);
+
+        let workstation = recommend_model(&machine(10, &["neon", "dotprod"], 32));
+        assert_eq!(workstation.model_id, "turbo");
+        assert_eq!(workstation.quantization.as_deref(), Some("q5_0"));
+
+        // Memory caps the recommendation however many cores there are.
+        let low_memory = recommend_model(&machine(8, &["avx2", "fma"], 4));
+        assert_eq!(low_memory.model_id, "small");
+        assert!(low_memory.reason.contains("too much"));
+        let many_cores = recommend_model(&machine(16, &["avx2", "fma"], 1));
+        assert_eq!(many_cores.model_id, "tiny");
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, Emitter, Manager};
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    engine::engine_for_config,
+    injector::deliver_text,
+    overlay::{emit_partial_transcript, set_overlay_visible},
+    pipeline::transcribe_and_deliver,
+    transcription::{is_cancellation, InferenceOptions},
+    types::{AppConfig, JobStatus, PartialTranscript, RecordingState, TranscriptionJob},
+};
+
+pub const TRANSCRIPTION_JOB_EVENT: &str = "transcription-job";
+pub const CANCELLED_MESSAGE: &str = "transcription cancelled";
+/// Previous dictations older than this are unrelated context and are not carried over.
+const CARRY_OVER_MAX_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// A finished capture together with the settings that were active when it was stopped.
+pub struct PendingJob {
+    pub job_id: String,
+    pub session_id: String,
+    pcm: Vec<f32>,
+    capture_ms: u128,
+    stopped_at: Instant,
+    config: AppConfig,
+    cancel: Arc<AtomicBool>,
+}
+
+impl PendingJob {
+    pub fn new(
+        session_id: String,
+        pcm: Vec<f32>,
+        capture_ms: u128,
+        stopped_at: Instant,
+        config: AppConfig,
+    ) -> Self {
+        Self {
+            job_id: Uuid::new_v4().to_string(),
+            session_id,
+            pcm,
+            capture_ms,
+            stopped_at,
+            config,
+            cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn summary(&self, status: JobStatus) -> TranscriptionJob {
+        TranscriptionJob {
+            job_id: self.job_id.clone(),
+            session_id: self.session_id.clone(),
+            status,
+            result: None,
+            error: None,
+        }
+    }
+}
+
+struct RunningJob {
+    job_id: String,
+    session_id: String,
+    cancel: Arc<AtomicBool>,
+}
+
+/// Finished captures in stop order. A single worker drains the queue so dictations are
+/// delivered in the order they were spoken.
+#[derive(Default)]
+pub struct JobQueue {
+    pending: VecDeque<PendingJob>,
+    running: Option<RunningJob>,
+    worker_active: bool,
+}
+
+impl JobQueue {
+    /// Queues a job; returns true when no worker is draining the queue and one must be started.
+    pub fn push(&mut self, job: PendingJob) -> bool {
+        self.pending.push_back(job);
+        !std::mem::replace(&mut self.worker_active, true)
+    }
+
+    /// Marks the oldest queued job as running, or retires the worker once the queue is empty.
+    pub fn start_next(&mut self) -> Option<PendingJob> {
+        self.running = None;
+        let job = self.pending.pop_front();
+        match &job {
+            Some(job) => {
+                self.running = Some(RunningJob {
+                    job_id: job.job_id.clone(),
+                    session_id: job.session_id.clone(),
+                    cancel: Arc::clone(&job.cancel),
+                });
+            }
+            None => self.worker_active = false,
+        }
+        job
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.worker_active
+    }
+
+    /// Aborts the running job; returns false when nothing is running.
+    pub fn cancel_running(&self) -> bool {
+        match &self.running {
+            Some(running) => {
+                running.cancel.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancels one job: queued jobs are dropped, the running one is aborted.
+    pub fn cancel(&mut self, job_id: &str) -> Option<TranscriptionJob> {
+        if let Some(index) = self.pending.iter().position(|job| job.job_id == job_id) {
+            let job = self.pending.remove(index)?;
+            return Some(job.summary(JobStatus::Cancelled));
+        }
+        let running = self.running.as_ref().filter(|job| job.job_id == job_id)?;
+        running.cancel.store(true, Ordering::Relaxed);
+        None
+    }
+
+    pub fn snapshot(&self) -> Vec<TranscriptionJob> {
+        let running = self.running.iter().map(|job| TranscriptionJob {
+            job_id: job.job_id.clone(),
+            session_id: job.session_id.clone(),
+            status: JobStatus::Running,
+            result: None,
+            error: None,
+        });
+        running
+            .chain(
+                self.pending
+                    .iter()
+                    .map(|job| job.summary(JobStatus::Queued)),
+            )
+            .collect()
+    }
+}
+
+pub fn emit_job(app: &AppHandle, job: &TranscriptionJob) {
+    let _ = app.emit(TRANSCRIPTION_JOB_EVENT, job);
+}
+
+/// Queues a finished capture and starts the worker if it is not already draining the queue.
+pub fn enqueue_job(app: &AppHandle, state: &AppState, job: PendingJob) -> TranscriptionJob {
+    let summary = job.summary(JobStatus::Queued);
+    let start_worker = state.with_lock(|s| {
+        if s.active_session.is_none() {
+            s.status.recording_state = RecordingState::Transcribing;
+        }
+        s.jobs.push(job)
+    });
+    emit_job(app, &summary);
+    if start_worker {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app.state::<AppState>();
+            loop {
+                let (next, idle) = state.with_lock(|s| {
+                    let next = s.jobs.start_next();
+                    let idle = next.is_none() && s.active_session.is_none();
+                    if idle {
+                        s.status.recording_state = RecordingState::Idle;
+                    }
+                    (next, idle)
+                });
+                match next {
+                    Some(job) => run_job(&app, &state, job).await,
+                    None => {
+                        if idle {
+                            set_overlay_visible(&app, false);
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+    }
+    summary
+}
+
+async fn run_job(app: &AppHandle, state: &AppState, job: PendingJob) {
+    let mut summary = job.summary(JobStatus::Running);
+    emit_job(app, &summary);
+
+    let options = state.with_lock(|s| {
+        let mut options = InferenceOptions::from_config(&job.config);
+        options.cancel = Arc::clone(&job.cancel);
+        // Read at run time so a queued dictation sees the one delivered just before it.
+        if job.config.carry_over_context {
+            options.previous_text = s
+                .last_transcript
+                .as_ref()
+                .filter(|(_, finished)| finished.elapsed() < CARRY_OVER_MAX_AGE)
+                .map(|(text, _)| text.clone());
+        }
+        options
+    });
+    let engine = engine_for_config(&job.config, &state.contexts);
+    let result = transcribe_and_deliver(
+        engine.as_ref(),
+        job.pcm,
+        &options,
+        &job.config.paste_mode,
+        job.stopped_at,
+        job.capture_ms,
+        deliver_text,
+    )
+    .await;
+
+    match result {
+        Ok(result) => {
+            if job.config.streaming_preview {
+                emit_partial_transcript(
+                    app,
+                    &PartialTranscript {
+                        session_id: job.session_id.clone(),
+                        committed_text: result.raw_text.clone(),
+                        tentative_text: String::new(),
+                        is_final: true,
+                    },
+                );
+            }
+            state.with_lock(|s| {
+                s.status.last_error = None;
+                if !result.raw_text.is_empty() {
+                    s.last_transcript = Some((result.raw_text.clone(), Instant::now()));
+                }
+            });
+            summary.status = JobStatus::Done;
+            summary.result = Some(result);
+        }
+        Err(err) if is_cancellation(&err) => {
+            summary.status = JobStatus::Cancelled;
+            summary.error = Some(CANCELLED_MESSAGE.to_string());
+        }
+        Err(err) => {
+            let message = err.to_string();
+            state.with_lock(|s| {
+                s.status.last_error = Some(message.clone());
+            });
+            summary.status = JobStatus::Failed;
+            summary.error = Some(message);
+        }
+    }
+    emit_job(app, &summary);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::{JobQueue, PendingJob};
+    use crate::{config::default_config, types::JobStatus};
+
+    fn job(session_id: &str) -> PendingJob {
+        PendingJob::new(
+            session_id.to_string(),
+            vec![0.0; 160],
+            0,
+            Instant::now(),
+            default_config(),
+        )
+    }
+
+    #[test]
+    fn drains_jobs_in_stop_order_with_one_worker() {
+        let mut queue = JobQueue::default();
+        assert!(queue.push(job("first")));
+        assert!(!queue.push(job("second")));
+
+        assert_eq!(queue.start_next().unwrap().session_id, "first");
+        assert!(!queue.push(job("third")));
+        assert_eq!(queue.start_next().unwrap().session_id, "second");
+        assert_eq!(queue.start_next().unwrap().session_id, "third");
+        assert!(queue.start_next().is_none());
+        assert!(!queue.is_busy());
+        assert!(queue.push(job("fourth")));
+    }
+
+    #[test]
+    fn cancels_queued_job_without_touching_running_one() {
+        let mut queue = JobQueue::default();
+        queue.push(job("running"));
+        let queued = job("queued");
+        let queued_id = queued.job_id.clone();
+        queue.push(queued);
+        let running = queue.start_next().unwrap();
+
+        let cancelled = queue.cancel(&queued_id).unwrap();
+        assert_eq!(cancelled.status, JobStatus::Cancelled);
+        assert_eq!(queue.snapshot().len(), 1);
+
+        assert!(queue.cancel(&running.job_id).is_none());
+        assert!(running.cancel.load(std::sync::atomic::Ordering::Relaxed));
+        assert!(queue.start_next().is_none());
+    }
+}
@@ -1,18 +1,33 @@
 mod app_state;
 mod audio;
+mod benchmark;
+mod chunking;
 mod command_parser;
 mod commands;
 mod config;
+mod context_cache;
+mod engine;
+mod filter;
+mod ggml;
+mod hardware;
 mod injector;
+mod jobs;
 mod model;
+mod model_registry;
+mod model_store;
 mod overlay;
+mod pipeline;
 mod platform;
+mod preflight;
+mod prompt;
+mod remote;
+mod streaming;
 mod transcription;
 mod types;
 
 use app_state::AppState;
 use tauri::{AppHandle, Manager, WindowEvent};
-use tauri_plugin_global_shortcut::ShortcutState;
+use tauri_plugin_global_shortcut::{Shortcut, ShortcutState};
 
 #[cfg(desktop)]
 use tauri::{
@@ -41,8 +56,9 @@ fn show_settings_window(app: &AppHandle) -> Result<(), String> {
 #[cfg(desktop)]
 fn refresh_tray_toggle_label(state: &AppState, toggle_item: &MenuItem<tauri::Wry>) {
     let next_text = state.with_lock(|s| match s.status.recording_state {
-        RecordingState::Idle => "Start Dictation",
-        RecordingState::Recording | RecordingState::Transcribing => "Stop Dictation",
+        // Earlier dictations transcribe in the background, so a new one can start meanwhile.
+        RecordingState::Idle | RecordingState::Transcribing => "Start Dictation",
+        RecordingState::Recording => "Stop Dictation",
     });
     let _ = toggle_item.set_text(next_text);
 }
@@ -61,6 +77,14 @@ fn trigger_toggle(app_handle: AppHandle, toggle_item: MenuItem<tauri::Wry>) {
     });
 }
 
+#[cfg(desktop)]
+fn trigger_cancel(app_handle: &AppHandle, toggle_item: &MenuItem<tauri::Wry>) {
+    let state = app_handle.state::<AppState>();
+    // Pressing cancel with nothing in flight is a no-op rather than an error.
+    let _ = commands::cancel_recording_internal(app_handle, &state);
+    refresh_tray_toggle_label(&state, toggle_item);
+}
+
 #[cfg(desktop)]
 fn setup_tray(app: &tauri::App) -> Result<MenuItem<tauri::Wry>, String> {
     let toggle =
@@ -119,6 +143,28 @@ fn setup_tray(app: &tauri::App) -> Result<MenuItem<tauri::Wry>, String> {
     Ok(toggle)
 }
 
+fn app_context() -> tauri::Context<tauri::Wry> {
+    tauri::generate_context!()
+}
+
+/// Entry point for `openspeak benchmark`, which runs the model benchmark without the UI.
+/// The bundled reference clip is looked up in the same resource directory the app uses.
+pub fn run_benchmark_cli(args: &[String]) -> i32 {
+    let result = tauri::utils::platform::resource_dir(
+        app_context().package_info(),
+        &tauri::Env::default(),
+    )
+    .map_err(anyhow::Error::from)
+    .and_then(|resource_dir| benchmark::run_cli(args, &resource_dir));
+    match result {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("benchmark failed: {err:#}");
+            1
+        }
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -134,21 +180,32 @@ pub fn run() {
         })
         .setup(|app| {
             overlay::setup_overlay_window(app)?;
+            context_cache::spawn_idle_unloader(app.handle().clone());
             #[cfg(desktop)]
             let tray_toggle_item = setup_tray(app)?;
 
             #[cfg(desktop)]
             {
                 let state = app.state::<AppState>();
-                let hotkey = state.with_lock(|s| s.config.hotkey.clone());
+                let (hotkey, cancel_hotkey) =
+                    state.with_lock(|s| (s.config.hotkey.clone(), s.config.cancel_hotkey.clone()));
+                let mut shortcuts: Vec<Shortcut> = vec![hotkey.parse()?];
+                shortcuts.extend(cancel_hotkey.parse::<Shortcut>().ok());
                 let hotkey_toggle_item = tray_toggle_item.clone();
                 app.handle().plugin(
                     tauri_plugin_global_shortcut::Builder::new()
-                        .with_shortcuts([hotkey.as_str()])?
-                        .with_handler(move |app, _shortcut, event| {
+                        .with_shortcuts(shortcuts)?
+                        .with_handler(move |app, shortcut, event| {
                             if event.state != ShortcutState::Pressed {
                                 return;
                             }
+                            // Read the cancel binding at press time so later changes apply.
+                            let state = app.state::<AppState>();
+                            let cancel_hotkey = state.with_lock(|s| s.config.cancel_hotkey.clone());
+                            if cancel_hotkey.parse::<Shortcut>().ok().as_ref() == Some(shortcut) {
+                                trigger_cancel(app, &hotkey_toggle_item);
+                                return;
+                            }
                             let app_handle = app.clone();
                             trigger_toggle(app_handle, hotkey_toggle_item.clone());
                         })
@@ -165,11 +222,40 @@ pub fn run() {
             commands::start_recording,
             commands::stop_recording,
             commands::toggle_recording,
+            commands::cancel_recording,
+            commands::cancel_transcription_job,
+            commands::get_transcription_jobs,
             commands::set_hotkey,
+            commands::set_cancel_hotkey,
             commands::set_paste_mode,
+            commands::set_language,
+            commands::set_word_timestamps,
+            commands::set_streaming_preview,
+            commands::set_engine,
+            commands::set_remote_engine,
+            commands::set_chunk_workers,
+            commands::set_output_filter,
+            commands::set_decoding,
+            commands::set_vocabulary,
+            commands::set_carry_over_context,
+            commands::set_task,
             commands::set_model,
-            commands::download_model
+            commands::set_model_variant,
+            commands::check_model,
+            commands::benchmark_models,
+            commands::set_download_config,
+            commands::get_model_catalog,
+            commands::set_model_unload_timeout,
+            commands::download_model,
+            commands::cancel_model_download,
+            commands::verify_model,
+            commands::list_models,
+            commands::inspect_model,
+            commands::delete_model,
+            commands::import_model,
+            commands::add_model_url,
+            commands::clean_partial_downloads
         ])
-        .run(tauri::generate_context!())
+        .run(app_context())
         .expect("error while running tauri application");
 }
@@ -1,5 +1,9 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("benchmark") {
+        std::process::exit(openspeak_lib::run_benchmark_cli(&args[1..]));
+    }
     openspeak_lib::run()
 }
@@ -1,19 +1,202 @@
-use std::{fs, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
-use anyhow::{Context, Result};
-use tokio::io::AsyncWriteExt;
+use anyhow::{anyhow, bail, Context, Result};
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use tokio::{
+    io::AsyncWriteExt,
+    sync::{watch, Notify},
+};
+
+use crate::{
+    ggml, model_registry, model_store,
+    types::{
+        AppConfig, CatalogModel, CustomModel, DownloadConfig, DownloadProgress, InstalledModel,
+        ModelHeader, ModelVariant, ModelVerification, PartialCleanup, VerificationStatus,
+    },
+};
 
 const APP_DATA_DIR: &str = "openspeak";
 const LEGACY_APP_DATA_DIR: &str = "brocks-dictation-tool";
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+pub const MODEL_DOWNLOAD_PROGRESS_EVENT: &str = "model-download-progress";
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Base model sizes offered in settings; each maps to one or more catalog variants.
+const MODEL_SIZES: &[&str] = &[
+    "tiny",
+    "base",
+    "small",
+    "small-tdrz",
+    "medium",
+    "large-v3",
+    "turbo",
+];
+const FULL_PRECISION: &str = "f16";
+const QUANTIZATIONS: &[&str] = &[FULL_PRECISION, "q5_0", "q5_1", "q8_0"];
+const HUGGINGFACE_BASE_URL: &str = "https://huggingface.co";
+const WHISPER_CPP_REPO: &str = "ggerganov/whisper.cpp";
+const TINYDIARIZE_REPO: &str = "akashmjn/tinydiarize-whisper.cpp";
+
+/// One downloadable build of a model size.
+struct CatalogEntry {
+    size: &'static str,
+    english_only: bool,
+    quantization: &'static str,
+    repo: &'static str,
+    file: &'static str,
+    /// Rough download size and peak memory while transcribing, in MB.
+    disk_mb: u32,
+    ram_mb: u32,
+}
+
+const fn entry(
+    size: &'static str,
+    english_only: bool,
+    quantization: &'static str,
+    file: &'static str,
+    disk_mb: u32,
+    ram_mb: u32,
+) -> CatalogEntry {
+    CatalogEntry {
+        size,
+        english_only,
+        quantization,
+        repo: WHISPER_CPP_REPO,
+        file,
+        disk_mb,
+        ram_mb,
+    }
+}
 
+// RAM figures follow the whisper.cpp memory table for full-precision weights; quantized
+// builds are scaled by their file size since the runtime buffers stay the same.
+const MODEL_CATALOG: &[CatalogEntry] = &[
+    entry("tiny", true, "f16", "ggml-tiny.en.bin", 75, 273),
+    entry("tiny", false, "f16", "ggml-tiny.bin", 75, 273),
+    entry("tiny", true, "q5_1", "ggml-tiny.en-q5_1.bin", 31, 230),
+    entry("tiny", false, "q5_1", "ggml-tiny-q5_1.bin", 31, 230),
+    entry("tiny", true, "q8_0", "ggml-tiny.en-q8_0.bin", 42, 240),
+    entry("tiny", false, "q8_0", "ggml-tiny-q8_0.bin", 42, 240),
+    entry("base", true, "f16", "ggml-base.en.bin", 142, 388),
+    entry("base", false, "f16", "ggml-base.bin", 142, 388),
+    entry("base", true, "q5_1", "ggml-base.en-q5_1.bin", 57, 300),
+    entry("base", false, "q5_1", "ggml-base-q5_1.bin", 57, 300),
+    entry("base", true, "q8_0", "ggml-base.en-q8_0.bin", 78, 325),
+    entry("base", false, "q8_0", "ggml-base-q8_0.bin", 78, 325),
+    entry("small", false, "f16", "ggml-small.bin", 466, 852),
+    entry("small", true, "f16", "ggml-small.en.bin", 466, 852),
+    entry("small", false, "q5_1", "ggml-small-q5_1.bin", 181, 570),
+    entry("small", true, "q5_1", "ggml-small.en-q5_1.bin", 181, 570),
+    entry("small", false, "q8_0", "ggml-small-q8_0.bin", 252, 640),
+    entry("small", true, "q8_0", "ggml-small.en-q8_0.bin", 252, 640),
+    CatalogEntry {
+        size: "small-tdrz",
+        english_only: true,
+        quantization: "f16",
+        repo: TINYDIARIZE_REPO,
+        file: "ggml-small.en-tdrz.bin",
+        disk_mb: 465,
+        ram_mb: 852,
+    },
+    entry("medium", false, "f16", "ggml-medium.bin", 1_500, 2_100),
+    entry("medium", true, "f16", "ggml-medium.en.bin", 1_500, 2_100),
+    entry("medium", false, "q5_0", "ggml-medium-q5_0.bin", 514, 1_110),
+    entry(
+        "medium",
+        true,
+        "q5_0",
+        "ggml-medium.en-q5_0.bin",
+        514,
+        1_110,
+    ),
+    entry("medium", false, "q8_0", "ggml-medium-q8_0.bin", 785, 1_390),
+    entry(
+        "medium",
+        true,
+        "q8_0",
+        "ggml-medium.en-q8_0.bin",
+        785,
+        1_390,
+    ),
+    entry("large-v3", false, "f16", "ggml-large-v3.bin", 2_950, 3_900),
+    entry(
+        "large-v3",
+        false,
+        "q5_0",
+        "ggml-large-v3-q5_0.bin",
+        1_080,
+        2_030,
+    ),
+    entry(
+        "turbo",
+        false,
+        "f16",
+        "ggml-large-v3-turbo.bin",
+        1_550,
+        2_200,
+    ),
+    entry(
+        "turbo",
+        false,
+        "q5_0",
+        "ggml-large-v3-turbo-q5_0.bin",
+        547,
+        1_200,
+    ),
+    entry(
+        "turbo",
+        false,
+        "q8_0",
+        "ggml-large-v3-turbo-q8_0.bin",
+        834,
+        1_490,
+    ),
+];
+
+/// Partial downloads untouched for this long belong to a failed download, not a running one.
+const STALE_PART_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Sizes, catalog ids and any custom model registered in the model root.
 pub fn is_supported_model(model_id: &str) -> bool {
-    matches!(
-        model_id,
-        "tiny" | "base" | "small" | "medium" | "large-v3" | "turbo"
-    )
+    MODEL_SIZES.contains(&model_id)
+        || catalog_entry(model_id).is_some()
+        || model_root().is_ok_and(|root| model_registry::find(&root, model_id).is_some())
+}
+
+fn model_id_in(file: &str, custom: &[CustomModel]) -> Option<String> {
+    MODEL_CATALOG
+        .iter()
+        .find(|entry| entry.file == file)
+        .map(catalog_id)
+        .or_else(|| {
+            custom
+                .iter()
+                .find(|model| model.file_name == file)
+                .map(|model| model.id.clone())
+        })
+}
+
+pub fn model_id_for_file(file: &str) -> Option<String> {
+    model_id_in(file, &model_registry::load(&model_root().ok()?))
 }
 
-fn model_root() -> Result<PathBuf> {
+/// Tinydiarize checkpoints emit speaker-turn tokens between segments.
+pub fn supports_speaker_turns(model_id: &str) -> bool {
+    model_id == "small-tdrz"
+}
+
+pub fn model_root() -> Result<PathBuf> {
     let mut dir = dirs::data_local_dir().context("failed to locate local data directory")?;
     let mut legacy = dir.clone();
     legacy.push(LEGACY_APP_DATA_DIR);
@@ -30,40 +213,394 @@ fn model_root() -> Result<PathBuf> {
     Ok(dir)
 }
 
-fn model_filename(model_id: &str) -> Option<&'static str> {
-    match model_id {
-        "tiny" => Some("ggml-tiny.en.bin"),
-        "base" => Some("ggml-base.en.bin"),
-        "small" => Some("ggml-small.bin"),
-        "medium" => Some("ggml-medium.bin"),
-        "large-v3" => Some("ggml-large-v3.bin"),
-        "turbo" => Some("ggml-large-v3-turbo.bin"),
-        _ => None,
-    }
-}
-
-fn model_url(model_id: &str) -> Option<&'static str> {
-    match model_id {
-        "tiny" => Some(
-            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.en.bin?download=true",
-        ),
-        "base" => Some(
-            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.en.bin?download=true",
-        ),
-        "small" => Some(
-            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin?download=true",
-        ),
-        "medium" => Some(
-            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.bin?download=true",
-        ),
-        "large-v3" => Some(
-            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3.bin?download=true",
-        ),
-        "turbo" => Some(
-            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-large-v3-turbo.bin?download=true",
-        ),
-        _ => None,
+/// Sizes other than tiny, base and the tinydiarize build default to multilingual weights.
+fn default_english_only(size: &str) -> bool {
+    matches!(size, "tiny" | "base" | "small-tdrz")
+}
+
+/// A size's default build keeps the bare size id; other builds append the language and
+/// quantization that differ from it, e.g. `medium-en-q5_0` or `tiny-multi`.
+fn catalog_id(entry: &CatalogEntry) -> String {
+    let mut id = entry.size.to_string();
+    if entry.english_only != default_english_only(entry.size) {
+        id.push_str(if entry.english_only { "-en" } else { "-multi" });
+    }
+    if entry.quantization != FULL_PRECISION {
+        id.push('-');
+        id.push_str(entry.quantization);
+    }
+    id
+}
+
+fn catalog_entry(model_id: &str) -> Option<&'static CatalogEntry> {
+    MODEL_CATALOG
+        .iter()
+        .find(|entry| catalog_id(entry) == model_id)
+}
+
+pub fn is_supported_quantization(quantization: &str) -> bool {
+    QUANTIZATIONS.contains(&quantization)
+}
+
+/// Catalog id for `model` in the given variant. Catalog and custom ids are already a single
+/// file and pass through unchanged.
+pub fn resolve_model(model: &str, variant: &ModelVariant) -> Result<String> {
+    if !MODEL_SIZES.contains(&model) {
+        if !is_supported_model(model) {
+            bail!("unknown model id: {model}");
+        }
+        return Ok(model.to_string());
+    }
+    let english_only = variant
+        .english_only
+        .unwrap_or_else(|| default_english_only(model));
+    let quantization = variant.quantization.as_deref().unwrap_or(FULL_PRECISION);
+    MODEL_CATALOG
+        .iter()
+        .find(|entry| {
+            entry.size == model
+                && entry.english_only == english_only
+                && entry.quantization == quantization
+        })
+        .map(catalog_id)
+        .with_context(|| {
+            let language = if english_only {
+                "English-only"
+            } else {
+                "multilingual"
+            };
+            format!("{model} has no {language} {quantization} build")
+        })
+}
+
+/// The model the config transcribes with. A hand-edited variant that does not exist falls
+/// back to the size's default build.
+pub fn active_model(config: &AppConfig) -> String {
+    resolve_model(&config.model_default, &config.model_variant)
+        .or_else(|_| resolve_model(&config.model_default, &ModelVariant::default()))
+        .unwrap_or_else(|_| config.model_default.clone())
+}
+
+/// Catalog estimates for a built-in build, in bytes.
+pub struct ModelRequirements {
+    pub download_bytes: u64,
+    pub ram_bytes: u64,
+}
+
+pub fn model_requirements(model_id: &str) -> Option<ModelRequirements> {
+    catalog_entry(model_id).map(|entry| ModelRequirements {
+        download_bytes: u64::from(entry.disk_mb) * 1_000_000,
+        ram_bytes: u64::from(entry.ram_mb) * 1_000_000,
+    })
+}
+
+/// Every built-in build with its size and memory estimates.
+pub fn model_catalog() -> Vec<CatalogModel> {
+    let root = model_root().ok();
+    MODEL_CATALOG
+        .iter()
+        .map(|entry| CatalogModel {
+            model_id: catalog_id(entry),
+            size: entry.size.to_string(),
+            english_only: entry.english_only,
+            quantization: entry.quantization.to_string(),
+            file_name: entry.file.to_string(),
+            disk_mb: entry.disk_mb,
+            ram_mb: entry.ram_mb,
+            installed: root
+                .as_ref()
+                .is_some_and(|root| root.join(entry.file).exists()),
+        })
+        .collect()
+}
+
+pub fn model_filename(model_id: &str) -> Option<String> {
+    if let Some(entry) = catalog_entry(model_id) {
+        return Some(entry.file.to_string());
+    }
+    let root = model_root().ok()?;
+    model_registry::find(&root, model_id).map(|model| model.file_name)
+}
+
+fn model_url(model_id: &str, download: &DownloadConfig) -> Option<String> {
+    if let Some(url) = download.url_overrides.get(model_id) {
+        return Some(url.trim().to_string());
+    }
+    if let Some(entry) = catalog_entry(model_id) {
+        let base = match download.mirror_base_url.trim() {
+            "" => HUGGINGFACE_BASE_URL,
+            mirror => mirror.trim_end_matches('/'),
+        };
+        return Some(format!(
+            "{base}/{}/resolve/main/{}?download=true",
+            entry.repo, entry.file
+        ));
+    }
+    let root = model_root().ok()?;
+    model_registry::find(&root, model_id)?.url
+}
+
+struct ManifestEntry {
+    file: &'static str,
+    size: u64,
+    sha256: &'static str,
+}
+
+/// Published size and SHA-256 of each downloadable model file.
+const MODEL_MANIFEST: &[ManifestEntry] = &[
+    ManifestEntry {
+        file: "ggml-tiny.en.bin",
+        size: 77_704_715,
+        sha256: "921e4cf8686fdd993dcd081a5da5b6c365bfde1162e72b08d75ac75289920b1f",
+    },
+    ManifestEntry {
+        file: "ggml-base.en.bin",
+        size: 147_964_211,
+        sha256: "a03779c86df3323075f5e796cb2ce5029f00ec8869eee3fdfb897afe36c6d002",
+    },
+    ManifestEntry {
+        file: "ggml-small.bin",
+        size: 487_601_967,
+        sha256: "1be3a9b2063867b937e64e2ec7483364a79917e157fa98c5d94b5c1fffea987b",
+    },
+    ManifestEntry {
+        file: "ggml-medium.bin",
+        size: 1_533_763_059,
+        sha256: "6c14d5adee5f86394037b4e4e8b59f1673b6cee10e3cf0b11bbdbee79c156208",
+    },
+    ManifestEntry {
+        file: "ggml-large-v3.bin",
+        size: 3_095_033_483,
+        sha256: "64d182b440b98d5203c4f9bd541544d84c605196c4f7b845dfa11fb23594d1e2",
+    },
+    ManifestEntry {
+        file: "ggml-large-v3-turbo.bin",
+        size: 1_624_555_275,
+        sha256: "1fc70f774d38eb169993ac391eea357ef47c88757ef72ee5943879b7e8e2bc69",
+    },
+];
+
+fn manifest_entry(file: &str) -> Option<&'static ManifestEntry> {
+    MODEL_MANIFEST.iter().find(|entry| entry.file == file)
+}
+
+/// What a model file is checked against: the shipped manifest for built-in models, or the
+/// checksum given when a custom model was added.
+struct ExpectedFile {
+    size: Option<u64>,
+    sha256: String,
+}
+
+fn expected_file(root: &Path, file: &str) -> Option<ExpectedFile> {
+    if let Some(entry) = manifest_entry(file) {
+        return Some(ExpectedFile {
+            size: Some(entry.size),
+            sha256: entry.sha256.to_string(),
+        });
+    }
+    let custom = model_registry::load(root)
+        .into_iter()
+        .find(|model| model.file_name == file)?;
+    custom
+        .sha256
+        .map(|sha256| ExpectedFile { size: None, sha256 })
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path).context("failed to open model file for hashing")?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 1 << 20];
+    loop {
+        let n = file
+            .read(&mut buf)
+            .context("failed to read model file for hashing")?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+/// Checks `path` against its expected size and checksum. The size is compared first so a
+/// truncated download is caught without hashing it.
+fn verify_file(
+    model_id: &str,
+    path: &Path,
+    expected: Option<ExpectedFile>,
+) -> Result<ModelVerification> {
+    let mut verification = ModelVerification {
+        model_id: model_id.to_string(),
+        status: VerificationStatus::Verified,
+        expected_size: expected.as_ref().and_then(|expected| expected.size),
+        actual_size: None,
+        expected_sha256: expected.as_ref().map(|expected| expected.sha256.clone()),
+        actual_sha256: None,
+    };
+    let Ok(metadata) = fs::metadata(path) else {
+        verification.status = VerificationStatus::Missing;
+        return Ok(verification);
+    };
+    verification.actual_size = Some(metadata.len());
+    let Some(expected) = expected else {
+        verification.status = VerificationStatus::Unlisted;
+        return Ok(verification);
+    };
+    if expected.size.is_some_and(|size| size != metadata.len()) {
+        verification.status = VerificationStatus::SizeMismatch;
+        return Ok(verification);
+    }
+    let actual = sha256_file(path)?;
+    if actual != expected.sha256 {
+        verification.status = VerificationStatus::ChecksumMismatch;
+    }
+    verification.actual_sha256 = Some(actual);
+    Ok(verification)
+}
+
+/// Hashes an installed model against the manifest. This reads the whole file, so it runs on
+/// a blocking thread.
+pub async fn verify_model(model_id: &str) -> Result<ModelVerification> {
+    let file = model_filename(model_id).context("unknown model id")?;
+    verify_and_record(model_id.to_string(), file).await
+}
+
+/// Verifies a file in the model root on a blocking thread and remembers the outcome for
+/// `list_models`.
+async fn verify_and_record(model_id: String, file: String) -> Result<ModelVerification> {
+    let root = model_root()?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = root.join(&file);
+        let verification = verify_file(&model_id, &path, expected_file(&root, &file))?;
+        if let Ok(metadata) = fs::metadata(&path) {
+            let _ = model_store::record_verification(&root, &file, verification.status, &metadata);
+        }
+        Ok(verification)
+    })
+    .await
+    .context("model verification task failed")?
+}
+
+/// Notes that a model was just loaded for transcription.
+pub fn mark_model_used(model_id: &str) {
+    if let (Some(file), Ok(root)) = (model_filename(model_id), model_root()) {
+        let _ = model_store::record_used(&root, &file);
+    }
+}
+
+/// Only plain `*.bin` names directly inside the model root are accepted from the UI.
+fn validate_model_file_name(file: &str) -> Result<()> {
+    if file.is_empty()
+        || file.starts_with('.')
+        || file.contains(['/', '\\'])
+        || !file.ends_with(".bin")
+    {
+        bail!("invalid model file name: {file}");
+    }
+    Ok(())
+}
+
+fn list_models_in(root: &Path, active_file: Option<&str>) -> Result<Vec<InstalledModel>> {
+    let records = model_store::load_records(root);
+    let custom = model_registry::load(root);
+    let mut models = Vec::new();
+    for entry in fs::read_dir(root).context("failed to read model directory")? {
+        let entry = entry.context("failed to read model directory entry")?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if validate_model_file_name(&file_name).is_err() {
+            continue;
+        }
+        let metadata = entry
+            .metadata()
+            .context("failed to read model file metadata")?;
+        if !metadata.is_file() {
+            continue;
+        }
+        let record = records.get(&file_name).cloned().unwrap_or_default();
+        models.push(InstalledModel {
+            model_id: model_id_in(&file_name, &custom),
+            active: active_file == Some(file_name.as_str()),
+            size_bytes: metadata.len(),
+            verification: record.verification_for(&metadata),
+            last_used_ms: record.last_used_ms,
+            file_name,
+        });
+    }
+    models.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    Ok(models)
+}
+
+/// Every `.bin` file in the model root, including ones OpenSpeak does not know about.
+pub fn list_models(active_model: &str) -> Result<Vec<InstalledModel>> {
+    list_models_in(&model_root()?, model_filename(active_model).as_deref())
+}
+
+/// Hashes one file in the model root and returns its refreshed listing entry.
+pub async fn inspect_model(file: &str, active_model: &str) -> Result<InstalledModel> {
+    validate_model_file_name(file)?;
+    let model_id = model_id_for_file(file).unwrap_or_else(|| file.to_string());
+    verify_and_record(model_id, file.to_string()).await?;
+    list_models(active_model)?
+        .into_iter()
+        .find(|model| model.file_name == file)
+        .context("model file not found")
+}
+
+pub fn delete_model_file(file: &str) -> Result<()> {
+    validate_model_file_name(file)?;
+    let root = model_root()?;
+    fs::remove_file(root.join(file)).context("failed to delete model file")?;
+    let _ = model_store::forget(&root, file);
+    // A custom model without its file cannot be used, so its registration goes too.
+    model_registry::unregister_file(&root, file)?;
+    Ok(())
+}
+
+fn clean_stale_parts_in(root: &Path, max_age: Duration) -> Result<PartialCleanup> {
+    let mut cleanup = PartialCleanup {
+        removed_files: Vec::new(),
+        freed_bytes: 0,
+    };
+    for entry in fs::read_dir(root).context("failed to read model directory")? {
+        let entry = entry.context("failed to read model directory entry")?;
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "part") {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .unwrap_or_default();
+        if age < max_age {
+            continue;
+        }
+        // Skip parts a download is still writing. Holding the lock ourselves keeps a new
+        // download of the same model out while the part is deleted.
+        let lock = fs::File::open(path.with_extension("lock")).ok();
+        if lock.as_ref().is_some_and(|lock| lock.try_lock().is_err()) {
+            continue;
+        }
+        if fs::remove_file(&path).is_ok() {
+            cleanup.freed_bytes += metadata.len();
+            cleanup
+                .removed_files
+                .push(entry.file_name().to_string_lossy().to_string());
+        }
     }
+    Ok(cleanup)
+}
+
+/// Removes `.part` files left behind by downloads that failed or were abandoned.
+pub fn clean_stale_parts() -> Result<PartialCleanup> {
+    clean_stale_parts_in(&model_root()?, STALE_PART_AGE)
 }
 
 pub fn model_path(model_id: &str) -> Result<PathBuf> {
@@ -80,45 +617,904 @@ pub fn is_model_installed(model_id: &str) -> bool {
     path.exists()
 }
 
-pub async fn ensure_model_async(model_id: &str) -> Result<PathBuf> {
+pub async fn ensure_model_async(model_id: &str, download: &DownloadConfig) -> Result<PathBuf> {
     if is_model_installed(model_id) {
         return model_path(model_id);
     }
-    let _ = download_model(model_id).await?;
+    let _ = download_model(model_id, download, |_| {}).await?;
     model_path(model_id)
 }
 
-pub async fn download_model(model_id: &str) -> Result<String> {
-    let path = model_path(model_id)?;
-    let url = model_url(model_id).context("unknown model id")?;
+/// Total size from a `Content-Range: bytes start-end/total` header.
+fn content_range_total(response: &reqwest::Response) -> Option<u64> {
+    let value = response
+        .headers()
+        .get(reqwest::header::CONTENT_RANGE)?
+        .to_str()
+        .ok()?;
+    value.rsplit_once('/')?.1.parse().ok()
+}
 
-    if path.exists() {
-        return Ok(format!("existing-{}", model_id));
+/// HTTP client for model downloads with the configured proxy and extra root certificates.
+pub fn download_client(download: &DownloadConfig) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder();
+    let proxy_url = download.proxy_url.trim();
+    if !proxy_url.is_empty() {
+        let proxy = reqwest::Proxy::all(proxy_url).context("invalid download proxy URL")?;
+        builder = builder.proxy(proxy);
     }
+    for path in &download.ca_cert_paths {
+        let pem = fs::read(path).with_context(|| format!("failed to read certificate {path}"))?;
+        let certificates = reqwest::Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("invalid PEM certificate in {path}"))?;
+        if certificates.is_empty() {
+            bail!("no certificates found in {path}");
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    builder.build().context("failed to build HTTP client")
+}
 
-    let tmp_path = path.with_extension("part");
-    let client = reqwest::Client::builder()
-        .build()
-        .context("failed to build HTTP client")?;
-    let bytes = client
-        .get(url)
+/// Streams `url` into `part_path`, continuing from the bytes already in the file when the
+/// server honours a Range request.
+async fn download_to_part(
+    client: &reqwest::Client,
+    url: &str,
+    part_path: &Path,
+    model_id: &str,
+    cancel: &DownloadCancel,
+    on_progress: &mut impl FnMut(&DownloadProgress),
+) -> Result<()> {
+    let mut offset = fs::metadata(part_path).map(|meta| meta.len()).unwrap_or(0);
+    let mut request = client.get(url);
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={offset}-"));
+    }
+    let mut response = request
         .send()
         .await
-        .context("failed to start model download")?
+        .context("failed to start model download")?;
+    if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        // Servers also answer 416 when the partial file already holds every byte.
+        if content_range_total(&response) == Some(offset) {
+            on_progress(&DownloadProgress {
+                model_id: model_id.to_string(),
+                downloaded_bytes: offset,
+                total_bytes: Some(offset),
+                bytes_per_sec: 0.0,
+                eta_secs: Some(0),
+                done: true,
+            });
+            return Ok(());
+        }
+        // Otherwise the partial file is not a prefix the server recognises; start over.
+        offset = 0;
+        response = client
+            .get(url)
+            .send()
+            .await
+            .context("failed to restart model download")?;
+    }
+    let mut response = response
         .error_for_status()
-        .context("model download failed with non-success status")?
-        .bytes()
-        .await
-        .context("failed to read model download body")?;
+        .context("model download failed with non-success status")?;
 
-    let mut file = tokio::fs::File::create(&tmp_path)
+    let resumed = offset > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    let total_bytes = if resumed {
+        content_range_total(&response)
+    } else {
+        offset = 0;
+        response.content_length()
+    };
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(part_path)
         .await
         .context("failed to create temporary model file")?;
-    file.write_all(&bytes)
-        .await
-        .context("failed while writing model file")?;
+
+    let started = Instant::now();
+    let mut last_report: Option<Instant> = None;
+    let mut received = 0u64;
+    loop {
+        // Raced against the read so a stalled connection still notices a cancel.
+        let chunk = tokio::select! {
+            biased;
+            () = cancel.cancelled() => return Err(DownloadCancelled.into()),
+            chunk = response.chunk() => chunk.context("failed to read model download body")?,
+        };
+        let done = chunk.is_none();
+        if let Some(chunk) = chunk {
+            file.write_all(&chunk)
+                .await
+                .context("failed while writing model file")?;
+            received += chunk.len() as u64;
+        }
+        if done || last_report.is_none_or(|at| at.elapsed() >= PROGRESS_INTERVAL) {
+            last_report = Some(Instant::now());
+            let elapsed = started.elapsed().as_secs_f64();
+            let bytes_per_sec = if elapsed > 0.0 {
+                received as f64 / elapsed
+            } else {
+                0.0
+            };
+            let downloaded_bytes = offset + received;
+            on_progress(&DownloadProgress {
+                model_id: model_id.to_string(),
+                downloaded_bytes,
+                total_bytes,
+                bytes_per_sec,
+                eta_secs: total_bytes.filter(|_| bytes_per_sec > 0.0).map(|total| {
+                    (total.saturating_sub(downloaded_bytes) as f64 / bytes_per_sec).ceil() as u64
+                }),
+                done,
+            });
+        }
+        if done {
+            break;
+        }
+    }
     file.flush().await.context("failed flushing model file")?;
+    Ok(())
+}
+
+/// Downloads `url` next to `path` as `.part`, resuming an earlier interrupted download, and
+/// only moves it into place once it passes verification and has a valid ggml header.
+async fn fetch_verified(
+    client: &reqwest::Client,
+    url: &str,
+    path: &Path,
+    model_id: &str,
+    expected: Option<ExpectedFile>,
+    cancel: &DownloadCancel,
+    on_progress: &mut impl FnMut(&DownloadProgress),
+) -> Result<ModelHeader> {
+    let tmp_path = path.with_extension("part");
+    download_to_part(client, url, &tmp_path, model_id, cancel, on_progress).await?;
+
+    let check_path = tmp_path.clone();
+    let model = model_id.to_string();
+    let (verification, header) = tauri::async_runtime::spawn_blocking(move || {
+        let verification = verify_file(&model, &check_path, expected)?;
+        anyhow::Ok((verification, ggml::read_header(&check_path)))
+    })
+    .await
+    .context("model verification task failed")??;
+    if !verification.status.is_usable() {
+        let _ = fs::remove_file(&tmp_path);
+        bail!(
+            "downloaded model {model_id} failed verification ({:?})",
+            verification.status
+        );
+    }
+    let header = match header {
+        Ok(header) => header,
+        Err(err) => {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(err.context(format!("downloaded model {model_id} is unusable")));
+        }
+    };
+
+    fs::rename(&tmp_path, path).context("failed finalizing downloaded model file")?;
+    if let (Some(root), Some(file), Ok(metadata)) = (
+        path.parent(),
+        path.file_name().and_then(|name| name.to_str()),
+        fs::metadata(path),
+    ) {
+        let _ = model_store::record_verification(root, file, verification.status, &metadata);
+    }
+    Ok(header)
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("model download cancelled")]
+pub struct DownloadCancelled;
+
+/// Cancellation signal for one download, checked between steps and awaited alongside reads.
+#[derive(Default)]
+struct DownloadCancel {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl DownloadCancel {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once `cancel` has been called, including before this was first polled.
+    async fn cancelled(&self) {
+        loop {
+            // Registered before the flag check so a cancel in between still wakes us.
+            let notified = self.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+type ProgressFn = Box<dyn FnMut(&DownloadProgress) + Send>;
+type DownloadOutcome = Option<Result<ModelHeader, String>>;
+
+struct InFlightDownload {
+    cancel: Arc<DownloadCancel>,
+    subscribers: Arc<Mutex<Vec<ProgressFn>>>,
+    outcome: watch::Receiver<DownloadOutcome>,
+}
+
+/// Downloads running in this process, keyed by model id.
+static DOWNLOADS: Mutex<BTreeMap<String, InFlightDownload>> = Mutex::new(BTreeMap::new());
+
+/// Held for the whole download so another OpenSpeak process never writes the same `.part`
+/// file. On Unix the lock file is unlinked on drop while still locked, so a waiter that then
+/// gets the lock on the orphaned file sees it is no longer current and retries on a fresh one.
+/// Elsewhere an open file cannot be unlinked cleanly and the lock file stays in place.
+struct DownloadLock {
+    _file: fs::File,
+    path: PathBuf,
+}
+
+impl Drop for DownloadLock {
+    fn drop(&mut self) {
+        if cfg!(unix) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Whether `file` is still the one at `path`.
+#[cfg(unix)]
+fn is_current_lock(file: &fs::File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (file.metadata(), fs::metadata(path)) {
+        (Ok(held), Ok(current)) => held.dev() == current.dev() && held.ino() == current.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_current_lock(_file: &fs::File, _path: &Path) -> bool {
+    true
+}
+
+fn open_lock_file(path: &Path) -> Result<fs::File> {
+    fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+        .context("failed to open model download lock")
+}
+
+async fn lock_download(path: &Path, cancel: &DownloadCancel) -> Result<DownloadLock> {
+    let lock_path = path.with_extension("lock");
+    loop {
+        let file = open_lock_file(&lock_path)?;
+        match file.try_lock() {
+            // The previous holder unlinked this file on release; retry on the current one.
+            Ok(()) if !is_current_lock(&file, &lock_path) => continue,
+            Ok(()) => {
+                return Ok(DownloadLock {
+                    _file: file,
+                    path: lock_path,
+                })
+            }
+            Err(fs::TryLockError::WouldBlock) => {}
+            Err(fs::TryLockError::Error(err)) => {
+                return Err(err).context("failed to lock model download")
+            }
+        }
+        tokio::select! {
+            () = cancel.cancelled() => return Err(DownloadCancelled.into()),
+            () = tokio::time::sleep(LOCK_RETRY_INTERVAL) => {}
+        }
+    }
+}
+
+async fn locked_fetch(
+    client: &reqwest::Client,
+    url: &str,
+    path: &Path,
+    model_id: &str,
+    expected: Option<ExpectedFile>,
+    cancel: &DownloadCancel,
+    on_progress: &mut impl FnMut(&DownloadProgress),
+) -> Result<ModelHeader> {
+    let _lock = lock_download(path, cancel).await?;
+    if path.exists() {
+        // Another process finished this download while we waited for the lock.
+        return ggml::read_header(path);
+    }
+    let result = fetch_verified(client, url, path, model_id, expected, cancel, on_progress).await;
+    if result.is_err() && cancel.is_cancelled() {
+        let _ = fs::remove_file(path.with_extension("part"));
+    }
+    result
+}
+
+/// Runs one download per model id at a time. A caller arriving while the same model is
+/// already downloading subscribes to its progress and waits for the same outcome.
+async fn coordinated_fetch(
+    client: reqwest::Client,
+    url: String,
+    path: PathBuf,
+    model_id: &str,
+    expected: Option<ExpectedFile>,
+    on_progress: impl FnMut(&DownloadProgress) + Send + 'static,
+) -> Result<ModelHeader> {
+    let mut outcome = {
+        let mut downloads = DOWNLOADS.lock();
+        if let Some(download) = downloads.get(model_id) {
+            download.subscribers.lock().push(Box::new(on_progress));
+            download.outcome.clone()
+        } else {
+            let (sender, receiver) = watch::channel(None);
+            let cancel = Arc::new(DownloadCancel::default());
+            let subscribers: Arc<Mutex<Vec<ProgressFn>>> =
+                Arc::new(Mutex::new(vec![Box::new(on_progress)]));
+            downloads.insert(
+                model_id.to_string(),
+                InFlightDownload {
+                    cancel: Arc::clone(&cancel),
+                    subscribers: Arc::clone(&subscribers),
+                    outcome: receiver.clone(),
+                },
+            );
+            let model_id = model_id.to_string();
+            tauri::async_runtime::spawn(async move {
+                let mut report = |progress: &DownloadProgress| {
+                    for subscriber in subscribers.lock().iter_mut() {
+                        subscriber(progress);
+                    }
+                };
+                let result = locked_fetch(
+                    &client,
+                    &url,
+                    &path,
+                    &model_id,
+                    expected,
+                    &cancel,
+                    &mut report,
+                )
+                .await;
+                {
+                    // A cancelled download is already gone from the map and may have been
+                    // replaced by a fresh one.
+                    let mut downloads = DOWNLOADS.lock();
+                    if downloads
+                        .get(&model_id)
+                        .is_some_and(|download| Arc::ptr_eq(&download.cancel, &cancel))
+                    {
+                        downloads.remove(&model_id);
+                    }
+                }
+                let _ = sender.send(Some(result.map_err(|err| err.to_string())));
+            });
+            receiver
+        }
+    };
+    let result = outcome
+        .wait_for(Option::is_some)
+        .await
+        .context("model download task stopped unexpectedly")?
+        .clone();
+    result
+        .context("model download task stopped unexpectedly")?
+        .map_err(|message| anyhow!(message))
+}
+
+/// Stops the in-flight download of `model_id` and removes its partial file. Returns false
+/// when nothing was downloading.
+pub fn cancel_download(model_id: &str) -> bool {
+    let Some(download) = DOWNLOADS.lock().remove(model_id) else {
+        return false;
+    };
+    download.cancel.cancel();
+    true
+}
+
+pub async fn download_model(
+    model_id: &str,
+    download: &DownloadConfig,
+    on_progress: impl FnMut(&DownloadProgress) + Send + 'static,
+) -> Result<String> {
+    let path = model_path(model_id)?;
+    let url = model_url(model_id, download).context("unknown model id")?;
 
-    fs::rename(&tmp_path, &path).context("failed finalizing downloaded model file")?;
+    if path.exists() {
+        return Ok(format!("existing-{}", model_id));
+    }
+
+    let file_name = model_filename(model_id).context("unknown model id")?;
+    let expected = expected_file(&model_root()?, &file_name);
+    let client = download_client(download)?;
+    coordinated_fetch(client, url, path, model_id, expected, on_progress).await?;
     Ok(format!("downloaded-{}", model_id))
 }
+
+/// Custom ids share the namespace of built-in ids and end up in the model file name.
+fn validate_custom_id(root: &Path, model_id: &str) -> Result<()> {
+    let valid_chars = model_id
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_' | '.'));
+    if model_id.is_empty() || model_id.len() > 64 || !valid_chars || model_id.starts_with('.') {
+        bail!("model id may only use lowercase letters, digits, '-', '_' and '.'");
+    }
+    let file_name = custom_file_name(model_id);
+    if MODEL_SIZES.contains(&model_id)
+        || catalog_entry(model_id).is_some()
+        || MODEL_CATALOG.iter().any(|entry| entry.file == file_name)
+        || model_registry::find(root, model_id).is_some()
+    {
+        bail!("model id {model_id} is already taken");
+    }
+    if root.join(&file_name).exists() {
+        bail!("a model file for {model_id} already exists");
+    }
+    Ok(())
+}
+
+fn custom_file_name(model_id: &str) -> String {
+    format!("ggml-{model_id}.bin")
+}
+
+/// `ggml-medium-finetune.bin` becomes `medium-finetune`.
+fn default_custom_id(source: &Path) -> String {
+    let stem = source
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    stem.strip_prefix("ggml-")
+        .unwrap_or(&stem)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Copies (or hard-links) a local ggml file into the model root and registers it under
+/// `model_id`, or an id derived from the file name.
+pub async fn import_model(
+    source: PathBuf,
+    model_id: Option<String>,
+    link: bool,
+) -> Result<CustomModel> {
+    let header = ggml::read_header(&source)?;
+    let root = model_root()?;
+    let model_id = model_id
+        .map(|id| id.trim().to_lowercase())
+        .unwrap_or_else(|| default_custom_id(&source));
+    validate_custom_id(&root, &model_id)?;
+
+    let file_name = custom_file_name(&model_id);
+    let target = root.join(&file_name);
+    let from = source.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if link {
+            fs::hard_link(&from, &target)
+                .context("failed to link model file; import a copy if it is on another drive")
+        } else {
+            fs::copy(&from, &target)
+                .map(|_| ())
+                .context("failed to copy model file")
+        }
+    })
+    .await
+    .context("model import task failed")??;
+
+    let model = CustomModel {
+        id: model_id,
+        file_name,
+        url: None,
+        sha256: None,
+        imported_from: Some(source.display().to_string()),
+        header,
+    };
+    model_registry::register(&root, model.clone())?;
+    Ok(model)
+}
+
+/// Downloads a model from `url`, checks it against `sha256` when given, and registers it.
+pub async fn add_model_from_url(
+    model_id: &str,
+    url: &str,
+    sha256: Option<String>,
+    download: &DownloadConfig,
+    on_progress: impl FnMut(&DownloadProgress) + Send + 'static,
+) -> Result<CustomModel> {
+    let url = url.trim();
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        bail!("model URL must start with http:// or https://");
+    }
+    let sha256 = sha256
+        .map(|sha| sha.trim().to_lowercase())
+        .filter(|sha| !sha.is_empty());
+    if let Some(sha) = &sha256 {
+        if sha.len() != 64 || !sha.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!("SHA-256 checksum must be 64 hex characters");
+        }
+    }
+    let root = model_root()?;
+    let model_id = model_id.trim().to_lowercase();
+    validate_custom_id(&root, &model_id)?;
+
+    let file_name = custom_file_name(&model_id);
+    let expected = sha256
+        .clone()
+        .map(|sha256| ExpectedFile { size: None, sha256 });
+    let client = download_client(download)?;
+    let header = coordinated_fetch(
+        client,
+        url.to_string(),
+        root.join(&file_name),
+        &model_id,
+        expected,
+        on_progress,
+    )
+    .await?;
+
+    let model = CustomModel {
+        id: model_id,
+        file_name,
+        url: Some(url.to_string()),
+        sha256,
+        imported_from: None,
+        header,
+    };
+    model_registry::register(&root, model.clone())?;
+    Ok(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::{Read, Write},
+        net::TcpListener,
+        path::Path,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    use super::{
+        catalog_entry, clean_stale_parts_in, coordinated_fetch, default_custom_id,
+        download_to_part, expected_file, list_models_in, lock_download, manifest_entry, model_url,
+        resolve_model, sha256_file, validate_custom_id, verify_file, DownloadCancel,
+        DownloadCancelled, ExpectedFile, MODEL_CATALOG,
+    };
+    use crate::types::{DownloadConfig, ModelVariant, VerificationStatus};
+
+    /// Serves `body` once, honouring a `Range: bytes=N-` header, and returns the request head.
+    /// A range starting at the end of `body` gets a 416 like real servers send.
+    fn range_server(body: &'static [u8]) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/model.bin", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !String::from_utf8_lossy(&request).contains("\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            let request = String::from_utf8_lossy(&request).to_string();
+            let start = request.lines().find_map(|line| {
+                line.to_ascii_lowercase()
+                    .strip_prefix("range: bytes=")?
+                    .strip_suffix('-')?
+                    .parse::<usize>()
+                    .ok()
+            });
+            let head = match start {
+                Some(start) if start >= body.len() => format!(
+                    "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Length: 0\r\nContent-Range: bytes */{}\r\n\r\n",
+                    body.len()
+                ),
+                Some(start) => format!(
+                    "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {start}-{}/{}\r\n\r\n",
+                    body.len() - start,
+                    body.len() - 1,
+                    body.len()
+                ),
+                None => format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len()),
+            };
+            stream.write_all(head.as_bytes()).unwrap();
+            stream
+                .write_all(&body[start.unwrap_or(0).min(body.len())..])
+                .unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn resumes_partial_download_with_range_request() {
+        let body: &'static [u8] = b"0123456789abcdefghij";
+        let part =
+            std::env::temp_dir().join(format!("openspeak-resume-{}.part", std::process::id()));
+        fs::write(&part, &body[..8]).unwrap();
+        let (url, server) = range_server(body);
+
+        let mut reports = Vec::new();
+        download_to_part(
+            &reqwest::Client::new(),
+            &url,
+            &part,
+            "tiny",
+            &DownloadCancel::default(),
+            &mut |progress| reports.push(progress.clone()),
+        )
+        .await
+        .unwrap();
+        let request = server.join().unwrap();
+
+        assert!(request.to_ascii_lowercase().contains("range: bytes=8-"));
+        assert_eq!(fs::read(&part).unwrap(), body);
+        let last = reports.last().unwrap();
+        assert!(last.done);
+        assert_eq!(last.downloaded_bytes, 20);
+        assert_eq!(last.total_bytes, Some(20));
+        fs::remove_file(&part).unwrap();
+    }
+
+    #[tokio::test]
+    async fn finishes_when_partial_download_is_already_complete() {
+        let body: &'static [u8] = b"0123456789";
+        let part =
+            std::env::temp_dir().join(format!("openspeak-complete-{}.part", std::process::id()));
+        fs::write(&part, body).unwrap();
+        let (url, server) = range_server(body);
+
+        let mut reports = Vec::new();
+        download_to_part(
+            &reqwest::Client::new(),
+            &url,
+            &part,
+            "tiny",
+            &DownloadCancel::default(),
+            &mut |progress| reports.push(progress.clone()),
+        )
+        .await
+        .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(fs::read(&part).unwrap(), body);
+        assert!(reports.last().unwrap().done);
+        assert_eq!(reports.last().unwrap().downloaded_bytes, 10);
+        fs::remove_file(&part).unwrap();
+    }
+
+    #[tokio::test]
+    async fn second_download_waits_for_the_lock_until_cancelled() {
+        let path = std::env::temp_dir().join(format!("openspeak-lock-{}.bin", std::process::id()));
+        let held = lock_download(&path, &DownloadCancel::default())
+            .await
+            .unwrap();
+
+        let cancel = DownloadCancel::default();
+        cancel.cancel();
+        let err = lock_download(&path, &cancel).await.unwrap_err();
+        assert!(err.is::<DownloadCancelled>());
+
+        drop(held);
+        let reacquired = lock_download(&path, &DownloadCancel::default())
+            .await
+            .unwrap();
+        drop(reacquired);
+        if cfg!(unix) {
+            assert!(!path.with_extension("lock").exists());
+        } else {
+            fs::remove_file(path.with_extension("lock")).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_downloads_of_a_model_share_one_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/model.bin", listener.local_addr().unwrap());
+        listener.set_nonblocking(true).unwrap();
+        let stop = Arc::new(AtomicBool::new(false));
+        let server = thread::spawn({
+            let stop = Arc::clone(&stop);
+            move || {
+                // magic, vocab, audio ctx/state/head/layer, text ctx/state/head/layer, mels, ftype
+                let body: Vec<u8> = [0x6767_6d6c, 51_865, 1_500, 384, 6, 4, 448, 384, 6, 4, 80, 1]
+                    .iter()
+                    .flat_map(|word: &i32| word.to_le_bytes())
+                    .collect();
+                let mut requests = 0;
+                while !stop.load(Ordering::SeqCst) {
+                    let Ok((mut stream, _)) = listener.accept() else {
+                        thread::sleep(Duration::from_millis(10));
+                        continue;
+                    };
+                    requests += 1;
+                    stream.set_nonblocking(false).unwrap();
+                    let _ = stream.read(&mut [0u8; 1024]);
+                    // Slow enough that the second caller arrives mid-download.
+                    thread::sleep(Duration::from_millis(200));
+                    let head = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", body.len());
+                    stream.write_all(head.as_bytes()).unwrap();
+                    stream.write_all(&body).unwrap();
+                }
+                requests
+            }
+        });
+
+        let dir = std::env::temp_dir().join(format!("openspeak-shared-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ggml-shared.bin");
+        let fetch = || {
+            coordinated_fetch(
+                reqwest::Client::new(),
+                url.clone(),
+                path.clone(),
+                "shared-test",
+                None,
+                |_| {},
+            )
+        };
+        let (first, second) = tokio::join!(fetch(), fetch());
+        stop.store(true, Ordering::SeqCst);
+
+        assert_eq!(server.join().unwrap(), 1);
+        assert_eq!(first.unwrap().n_vocab, 51_865);
+        assert_eq!(second.unwrap().n_vocab, 51_865);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn hashes_and_checks_model_files_against_manifest() {
+        let path = std::env::temp_dir().join(format!("openspeak-verify-{}", std::process::id()));
+        fs::write(&path, b"abc").unwrap();
+
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let tiny = || expected_file(&std::env::temp_dir(), "ggml-tiny.en.bin");
+        let truncated = verify_file("tiny", &path, tiny()).unwrap();
+        assert_eq!(truncated.status, VerificationStatus::SizeMismatch);
+        assert_eq!(truncated.actual_size, Some(3));
+        let unlisted = verify_file("custom", &path, None).unwrap();
+        assert_eq!(unlisted.status, VerificationStatus::Unlisted);
+        let checksum_only = ExpectedFile {
+            size: None,
+            sha256: "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string(),
+        };
+        let custom = verify_file("custom", &path, Some(checksum_only)).unwrap();
+        assert_eq!(custom.status, VerificationStatus::Verified);
+
+        fs::remove_file(&path).unwrap();
+        let missing = verify_file("tiny", &path, tiny()).unwrap();
+        assert_eq!(missing.status, VerificationStatus::Missing);
+    }
+
+    #[test]
+    fn lists_known_and_unknown_models_and_cleans_partials() {
+        let root = std::env::temp_dir().join(format!("openspeak-models-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("ggml-small.bin"), b"small").unwrap();
+        fs::write(root.join("ggml-custom-q5.bin"), b"custom").unwrap();
+        fs::write(root.join("ggml-medium.part"), b"half").unwrap();
+        fs::write(root.join("notes.txt"), b"not a model").unwrap();
+
+        let models = list_models_in(&root, Some("ggml-small.bin")).unwrap();
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].file_name, "ggml-custom-q5.bin");
+        assert_eq!(models[0].model_id, None);
+        assert_eq!(models[1].model_id.as_deref(), Some("small"));
+        assert!(models[1].active);
+        assert_eq!(models[1].size_bytes, 5);
+
+        let kept = clean_stale_parts_in(&root, Duration::from_secs(3600)).unwrap();
+        assert!(kept.removed_files.is_empty());
+        let lock = fs::File::create(root.join("ggml-medium.lock")).unwrap();
+        lock.lock().unwrap();
+        let in_use = clean_stale_parts_in(&root, Duration::ZERO).unwrap();
+        assert!(in_use.removed_files.is_empty());
+        drop(lock);
+        let cleanup = clean_stale_parts_in(&root, Duration::ZERO).unwrap();
+        assert_eq!(cleanup.removed_files, vec!["ggml-medium.part"]);
+        assert_eq!(cleanup.freed_bytes, 4);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn resolves_sizes_to_catalog_builds() {
+        let variant = |quantization: Option<&str>, english_only: Option<bool>| ModelVariant {
+            quantization: quantization.map(str::to_string),
+            english_only,
+        };
+        let resolve = |size: &str, v: &ModelVariant| resolve_model(size, v).unwrap();
+
+        assert_eq!(resolve("tiny", &variant(None, None)), "tiny");
+        assert_eq!(resolve("small", &variant(None, None)), "small");
+        assert_eq!(resolve("tiny", &variant(None, Some(false))), "tiny-multi");
+        assert_eq!(
+            resolve("medium", &variant(Some("q5_0"), Some(true))),
+            "medium-en-q5_0"
+        );
+        assert_eq!(resolve("base", &variant(Some("q8_0"), None)), "base-q8_0");
+        assert_eq!(
+            catalog_entry("medium-en-q5_0").unwrap().file,
+            "ggml-medium.en-q5_0.bin"
+        );
+        assert_eq!(catalog_entry("tiny").unwrap().file, "ggml-tiny.en.bin");
+        assert!(resolve_model("large-v3", &variant(None, Some(true))).is_err());
+        assert!(resolve_model("medium", &variant(Some("q5_1"), None)).is_err());
+    }
+
+    #[test]
+    fn builds_download_urls_from_mirror_and_overrides() {
+        let mut download = DownloadConfig::default();
+        assert_eq!(
+            model_url("small-tdrz", &download).unwrap(),
+            "https://huggingface.co/akashmjn/tinydiarize-whisper.cpp/resolve/main/ggml-small.en-tdrz.bin?download=true"
+        );
+        download.mirror_base_url = "https://hf-mirror.example/".to_string();
+        assert_eq!(
+            model_url("base-q8_0", &download).unwrap(),
+            "https://hf-mirror.example/ggerganov/whisper.cpp/resolve/main/ggml-base.en-q8_0.bin?download=true"
+        );
+        download.url_overrides.insert(
+            "base-q8_0".to_string(),
+            "https://models.internal/base.bin".to_string(),
+        );
+        assert_eq!(
+            model_url("base-q8_0", &download).unwrap(),
+            "https://models.internal/base.bin"
+        );
+    }
+
+    #[test]
+    fn derives_custom_ids_from_file_names() {
+        assert_eq!(
+            default_custom_id(Path::new("/tmp/ggml-medium-q5_0.bin")),
+            "medium-q5_0"
+        );
+        assert_eq!(
+            default_custom_id(Path::new("My Model (fine-tuned).bin")),
+            "my-model--fine-tuned-"
+        );
+    }
+
+    #[test]
+    fn every_catalog_file_has_a_manifest_entry() {
+        let missing: Vec<_> = MODEL_CATALOG
+            .iter()
+            .map(|entry| entry.file)
+            .filter(|file| manifest_entry(file).is_none())
+            .collect();
+        assert!(missing.is_empty(), "no manifest entry for {missing:?}");
+    }
+
+    #[test]
+    fn rejects_custom_ids_that_collide_with_catalog_files() {
+        let root = std::env::temp_dir().join(format!("openspeak-custom-{}", std::process::id()));
+        for taken in ["tiny.en", "small.en-q5_1", "medium-q5_0"] {
+            assert!(validate_custom_id(&root, taken).is_err(), "{taken}");
+        }
+        assert!(validate_custom_id(&root, "medium-finetune").is_ok());
+    }
+}
@@ -0,0 +1,42 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+
+use crate::types::CustomModel;
+
+const REGISTRY_FILE: &str = "custom-models.json";
+
+/// Models the user imported or added by URL, stored next to the model files.
+pub fn load(root: &Path) -> Vec<CustomModel> {
+    fs::read_to_string(root.join(REGISTRY_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save(root: &Path, models: &[CustomModel]) -> Result<()> {
+    let content =
+        serde_json::to_string_pretty(models).context("failed to serialize model registry")?;
+    fs::write(root.join(REGISTRY_FILE), content).context("failed to write model registry")
+}
+
+pub fn find(root: &Path, model_id: &str) -> Option<CustomModel> {
+    load(root).into_iter().find(|model| model.id == model_id)
+}
+
+pub fn register(root: &Path, model: CustomModel) -> Result<()> {
+    let mut models = load(root);
+    models.retain(|existing| existing.id != model.id);
+    models.push(model);
+    save(root, &models)
+}
+
+pub fn unregister_file(root: &Path, file_name: &str) -> Result<()> {
+    let mut models = load(root);
+    let before = models.len();
+    models.retain(|model| model.file_name != file_name);
+    if models.len() == before {
+        return Ok(());
+    }
+    save(root, &models)
+}
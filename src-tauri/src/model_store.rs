@@ -0,0 +1,145 @@
+use std::{
+    collections::HashMap,
+    fs::{self, Metadata},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::types::VerificationStatus;
+
+const STORE_FILE: &str = "models.json";
+
+// Serializes read-modify-write cycles on the store file.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Outcome of the last verification, valid only while the file keeps the same size and mtime.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationRecord {
+    pub status: VerificationStatus,
+    pub size: u64,
+    pub modified_ms: u64,
+}
+
+/// Bookkeeping for one file in the model root, keyed by file name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelRecord {
+    #[serde(default)]
+    pub last_used_ms: Option<u64>,
+    #[serde(default)]
+    pub verification: Option<VerificationRecord>,
+}
+
+impl ModelRecord {
+    pub fn verification_for(&self, metadata: &Metadata) -> Option<VerificationStatus> {
+        self.verification
+            .as_ref()
+            .filter(|record| {
+                record.size == metadata.len() && record.modified_ms == modified_ms(metadata)
+            })
+            .map(|record| record.status)
+    }
+}
+
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+pub fn modified_ms(metadata: &Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+/// A missing or unreadable store is treated as empty; it only holds derived information.
+pub fn load_records(root: &Path) -> HashMap<String, ModelRecord> {
+    fs::read_to_string(root.join(STORE_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn update(root: &Path, f: impl FnOnce(&mut HashMap<String, ModelRecord>)) -> Result<()> {
+    let _guard = STORE_LOCK.lock();
+    let mut records = load_records(root);
+    f(&mut records);
+    let content =
+        serde_json::to_string_pretty(&records).context("failed to serialize model records")?;
+    fs::write(root.join(STORE_FILE), content).context("failed to write model records")
+}
+
+pub fn record_used(root: &Path, file: &str) -> Result<()> {
+    update(root, |records| {
+        records.entry(file.to_string()).or_default().last_used_ms = Some(now_ms());
+    })
+}
+
+pub fn record_verification(
+    root: &Path,
+    file: &str,
+    status: VerificationStatus,
+    metadata: &Metadata,
+) -> Result<()> {
+    update(root, |records| {
+        records.entry(file.to_string()).or_default().verification = Some(VerificationRecord {
+            status,
+            size: metadata.len(),
+            modified_ms: modified_ms(metadata),
+        });
+    })
+}
+
+pub fn forget(root: &Path, file: &str) -> Result<()> {
+    update(root, |records| {
+        records.remove(file);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{forget, load_records, record_used, record_verification};
+    use crate::types::VerificationStatus;
+
+    #[test]
+    fn verification_is_dropped_once_the_file_changes() {
+        let root = std::env::temp_dir().join(format!("openspeak-store-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let model = root.join("ggml-test.bin");
+        fs::write(&model, b"weights").unwrap();
+
+        record_used(&root, "ggml-test.bin").unwrap();
+        let metadata = fs::metadata(&model).unwrap();
+        record_verification(
+            &root,
+            "ggml-test.bin",
+            VerificationStatus::Verified,
+            &metadata,
+        )
+        .unwrap();
+        let record = load_records(&root).remove("ggml-test.bin").unwrap();
+        assert!(record.last_used_ms.is_some());
+        assert_eq!(
+            record.verification_for(&metadata),
+            Some(VerificationStatus::Verified)
+        );
+
+        fs::write(&model, b"different weights").unwrap();
+        let changed = fs::metadata(&model).unwrap();
+        assert_eq!(record.verification_for(&changed), None);
+
+        forget(&root, "ggml-test.bin").unwrap();
+        assert!(load_records(&root).is_empty());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,12 +1,15 @@
 use tauri::{
-    App, AppHandle, Manager, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindowBuilder,
-    WindowEvent,
+    App, AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, WebviewUrl,
+    WebviewWindowBuilder, WindowEvent,
 };
 
+use crate::types::PartialTranscript;
+
 const OVERLAY_LABEL: &str = "recording-overlay";
 const OVERLAY_WIDTH: f64 = 420.0;
 const OVERLAY_HEIGHT: f64 = 132.0;
 const BOTTOM_MARGIN: f64 = 28.0;
+const PARTIAL_TRANSCRIPT_EVENT: &str = "partial-transcript";
 
 pub fn setup_overlay_window(app: &App) -> Result<(), String> {
     #[cfg(desktop)]
@@ -70,3 +73,7 @@ pub fn set_overlay_visible(app: &AppHandle, visible: bool) {
         }
     }
 }
+
+pub fn emit_partial_transcript(app: &AppHandle, transcript: &PartialTranscript) {
+    let _ = app.emit_to(OVERLAY_LABEL, PARTIAL_TRANSCRIPT_EVENT, transcript);
+}
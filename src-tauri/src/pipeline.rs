@@ -0,0 +1,180 @@
+use std::time::Instant;
+
+use anyhow::Result;
+
+use crate::{
+    command_parser::apply_basic_commands,
+    engine::TranscriptionEngine,
+    filter::filter_output,
+    transcription::{
+        is_low_confidence, InferenceOptions, TranscriptionCancelled, AUTO_LANGUAGE,
+        TRANSCRIBE_TASK, TRANSLATE_TASK,
+    },
+    types::TranscriptionResult,
+};
+
+/// Transcribes a finished capture, applies spoken commands and hands the text to `deliver`.
+/// `started` and `capture_ms` feed the reported end-to-end latency.
+pub async fn transcribe_and_deliver(
+    engine: &dyn TranscriptionEngine,
+    pcm: Vec<f32>,
+    options: &InferenceOptions,
+    paste_mode: &str,
+    started: Instant,
+    capture_ms: u128,
+    deliver: impl FnOnce(&str, &str) -> Result<String>,
+) -> Result<TranscriptionResult> {
+    let whisper = filter_output(engine.transcribe(pcm, options).await?, &options.filter);
+    // Engines that cannot be interrupted still must not deliver after a cancel.
+    if options.is_cancelled() {
+        return Err(TranscriptionCancelled.into());
+    }
+    let parsed = apply_basic_commands(&whisper.text);
+    let low_confidence = is_low_confidence(&whisper);
+    // Low-confidence text is only copied so it never lands in a document unreviewed.
+    let paste_mode = if low_confidence {
+        "clipboard"
+    } else {
+        paste_mode
+    };
+    let delivery = deliver(&parsed.transformed_text, paste_mode)?;
+    let source_language = whisper
+        .detected_language
+        .clone()
+        .or_else(|| (options.language != AUTO_LANGUAGE).then(|| options.language.clone()));
+
+    Ok(TranscriptionResult {
+        raw_text: whisper.text,
+        transformed_text: parsed.transformed_text,
+        commands_applied: parsed.commands_applied,
+        latency_ms: started.elapsed().as_millis() + capture_ms,
+        confidence: whisper.confidence,
+        low_confidence,
+        segments: whisper.segments,
+        delivery,
+        engine: engine.name().to_string(),
+        task: if options.translate {
+            TRANSLATE_TASK
+        } else {
+            TRANSCRIBE_TASK
+        }
+        .to_string(),
+        source_language,
+        detected_language: whisper.detected_language,
+        language_probability: whisper.language_probability,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::atomic::Ordering, time::Instant};
+
+    use super::transcribe_and_deliver;
+    use crate::{
+        config::default_config,
+        engine::MockEngine,
+        transcription::{is_cancellation, InferenceOptions, TRANSLATE_TASK},
+    };
+
+    fn options() -> InferenceOptions {
+        InferenceOptions::from_config(&default_config())
+    }
+
+    #[tokio::test]
+    async fn runs_capture_through_mock_engine_to_delivery() {
+        let engine = MockEngine {
+            transcript: "ship it comma then test period".to_string(),
+        };
+        let pcm = vec![0.1; 16_000];
+        let mut delivered = None;
+        let result = transcribe_and_deliver(
+            &engine,
+            pcm,
+            &options(),
+            "auto-paste",
+            Instant::now(),
+            0,
+            |text, mode| {
+                delivered = Some((text.to_string(), mode.to_string()));
+                Ok(mode.to_string())
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.transformed_text, "ship it, then test.");
+        assert_eq!(result.commands_applied, vec!["comma", "period"]);
+        assert_eq!(result.delivery, "auto-paste");
+        assert_eq!(result.engine, "mock");
+        assert_eq!(result.segments[0].end_ms, 1_000);
+        assert_eq!(
+            delivered,
+            Some(("ship it, then test.".to_string(), "auto-paste".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_capture_delivers_nothing_to_paste() {
+        let engine = MockEngine::default();
+        let result = transcribe_and_deliver(
+            &engine,
+            Vec::new(),
+            &options(),
+            "clipboard",
+            Instant::now(),
+            0,
+            |_, mode| Ok(mode.to_string()),
+        )
+        .await
+        .unwrap();
+
+        assert!(result.raw_text.is_empty());
+        assert!(!result.low_confidence);
+    }
+
+    #[tokio::test]
+    async fn reports_translate_task_and_configured_source_language() {
+        let engine = MockEngine::default();
+        let mut config = default_config();
+        config.language = "de".to_string();
+        config.task = TRANSLATE_TASK.to_string();
+        let result = transcribe_and_deliver(
+            &engine,
+            vec![0.1; 16_000],
+            &InferenceOptions::from_config(&config),
+            "clipboard",
+            Instant::now(),
+            0,
+            |_, mode| Ok(mode.to_string()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.task, "translate");
+        assert_eq!(result.source_language.as_deref(), Some("de"));
+    }
+
+    #[tokio::test]
+    async fn cancelled_transcription_is_not_delivered() {
+        let engine = MockEngine::default();
+        let options = options();
+        options.cancel.store(true, Ordering::Relaxed);
+        let mut delivered = false;
+        let result = transcribe_and_deliver(
+            &engine,
+            vec![0.1; 16_000],
+            &options,
+            "clipboard",
+            Instant::now(),
+            0,
+            |_, mode| {
+                delivered = true;
+                Ok(mode.to_string())
+            },
+        )
+        .await;
+
+        assert!(is_cancellation(&result.unwrap_err()));
+        assert!(!delivered);
+    }
+}
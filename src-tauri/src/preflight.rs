@@ -0,0 +1,218 @@
+use std::{fs, path::Path};
+
+use anyhow::Result;
+use sysinfo::{Disks, System};
+
+use crate::{
+    model::{is_model_installed, model_path, model_requirements, model_root, ModelRequirements},
+    types::{ModelPreflight, PreflightIssue, PreflightIssueKind, PreflightSeverity},
+};
+
+/// Space to leave free on top of a download so the disk is not filled to the last byte.
+const DISK_HEADROOM_BYTES: u64 = 512 * 1_000_000;
+
+pub struct SystemResources {
+    pub free_disk_bytes: Option<u64>,
+    pub total_memory_bytes: u64,
+    pub available_memory_bytes: u64,
+}
+
+/// Free space on the disk holding `path`, taken from the mount point that contains it.
+fn free_space(path: &Path) -> Option<u64> {
+    let path = fs::canonicalize(path).ok()?;
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+pub fn system_resources(model_root: &Path) -> SystemResources {
+    let mut system = System::new();
+    system.refresh_memory();
+    SystemResources {
+        free_disk_bytes: free_space(model_root),
+        total_memory_bytes: system.total_memory(),
+        available_memory_bytes: system.available_memory(),
+    }
+}
+
+fn gigabytes(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / 1e9)
+}
+
+fn evaluate(
+    model_id: &str,
+    requirements: Option<ModelRequirements>,
+    installed: bool,
+    partial_bytes: u64,
+    resources: &SystemResources,
+) -> ModelPreflight {
+    let mut issues = Vec::new();
+    let download_bytes = requirements.as_ref().map(|requirements| {
+        if installed {
+            0
+        } else {
+            requirements.download_bytes.saturating_sub(partial_bytes)
+        }
+    });
+
+    if let (Some(needed), Some(free)) = (
+        download_bytes.filter(|bytes| *bytes > 0),
+        resources.free_disk_bytes,
+    ) {
+        if free < needed {
+            issues.push(PreflightIssue {
+                kind: PreflightIssueKind::InsufficientDisk,
+                severity: PreflightSeverity::Error,
+                message: format!(
+                    "{model_id} needs about {} of disk space but only {} is free",
+                    gigabytes(needed),
+                    gigabytes(free)
+                ),
+                required_bytes: needed,
+                available_bytes: free,
+            });
+        } else if free < needed + DISK_HEADROOM_BYTES {
+            issues.push(PreflightIssue {
+                kind: PreflightIssueKind::LowDisk,
+                severity: PreflightSeverity::Warning,
+                message: format!(
+                    "downloading {model_id} leaves less than {} of disk space free",
+                    gigabytes(DISK_HEADROOM_BYTES)
+                ),
+                required_bytes: needed,
+                available_bytes: free,
+            });
+        }
+    }
+
+    let ram_bytes = requirements
+        .as_ref()
+        .map(|requirements| requirements.ram_bytes);
+    if let Some(needed) = ram_bytes {
+        if needed > resources.total_memory_bytes {
+            issues.push(PreflightIssue {
+                kind: PreflightIssueKind::InsufficientMemory,
+                severity: PreflightSeverity::Error,
+                message: format!(
+                    "{model_id} needs about {} of memory but this machine has {}",
+                    gigabytes(needed),
+                    gigabytes(resources.total_memory_bytes)
+                ),
+                required_bytes: needed,
+                available_bytes: resources.total_memory_bytes,
+            });
+        } else if needed > resources.available_memory_bytes {
+            issues.push(PreflightIssue {
+                kind: PreflightIssueKind::LowMemory,
+                severity: PreflightSeverity::Warning,
+                message: format!(
+                    "{model_id} needs about {} of memory and only {} is available; \
+                     transcription may be slow while other apps are open",
+                    gigabytes(needed),
+                    gigabytes(resources.available_memory_bytes)
+                ),
+                required_bytes: needed,
+                available_bytes: resources.available_memory_bytes,
+            });
+        }
+    }
+
+    ModelPreflight {
+        model_id: model_id.to_string(),
+        installed,
+        download_bytes,
+        ram_bytes,
+        free_disk_bytes: resources.free_disk_bytes,
+        total_memory_bytes: resources.total_memory_bytes,
+        available_memory_bytes: resources.available_memory_bytes,
+        issues,
+    }
+}
+
+/// Checks free space in the model root and memory against the catalog estimates for
+/// `model_id`. Custom models have no estimates and always pass.
+pub fn preflight_model(model_id: &str) -> Result<ModelPreflight> {
+    let root = model_root()?;
+    let installed = is_model_installed(model_id);
+    let partial_bytes = model_path(model_id)
+        .ok()
+        .and_then(|path| fs::metadata(path.with_extension("part")).ok())
+        .map_or(0, |metadata| metadata.len());
+    Ok(evaluate(
+        model_id,
+        model_requirements(model_id),
+        installed,
+        partial_bytes,
+        &system_resources(&root),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluate, SystemResources};
+    use crate::{
+        model::ModelRequirements,
+        types::{PreflightIssueKind, PreflightSeverity},
+    };
+
+    const GB: u64 = 1_000_000_000;
+
+    fn large() -> Option<ModelRequirements> {
+        Some(ModelRequirements {
+            download_bytes: 3 * GB,
+            ram_bytes: 4 * GB,
+        })
+    }
+
+    #[test]
+    fn flags_missing_disk_space_and_memory() {
+        let laptop = SystemResources {
+            free_disk_bytes: Some(2 * GB),
+            total_memory_bytes: 8 * GB,
+            available_memory_bytes: 3 * GB,
+        };
+        let preflight = evaluate("large-v3", large(), false, 0, &laptop);
+        let kinds: Vec<_> = preflight.issues.iter().map(|issue| issue.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                PreflightIssueKind::InsufficientDisk,
+                PreflightIssueKind::LowMemory
+            ]
+        );
+        assert_eq!(
+            preflight.blocking_issue().unwrap().kind,
+            PreflightIssueKind::InsufficientDisk
+        );
+
+        // A partial download only needs the remaining bytes, and installed models none.
+        let resumed = evaluate("large-v3", large(), false, 2 * GB, &laptop);
+        assert_eq!(resumed.download_bytes, Some(GB));
+        assert_eq!(resumed.issues[0].kind, PreflightIssueKind::LowDisk);
+        assert_eq!(resumed.issues[0].severity, PreflightSeverity::Warning);
+        let installed = evaluate("large-v3", large(), true, 0, &laptop);
+        assert!(installed.blocking_issue().is_none());
+    }
+
+    #[test]
+    fn blocks_models_larger_than_total_memory() {
+        let small_machine = SystemResources {
+            free_disk_bytes: Some(100 * GB),
+            total_memory_bytes: 2 * GB,
+            available_memory_bytes: GB,
+        };
+        let preflight = evaluate("large-v3", large(), false, 0, &small_machine);
+        assert_eq!(preflight.issues.len(), 1);
+        assert_eq!(
+            preflight.issues[0].kind,
+            PreflightIssueKind::InsufficientMemory
+        );
+        assert!(evaluate("custom", None, false, 0, &small_machine)
+            .issues
+            .is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+/// Whisper accepts at most half of its 448-token text context as prompt.
+pub const PROMPT_TOKEN_BUDGET: usize = 224;
+pub const MAX_VOCABULARY_TERMS: usize = 200;
+pub const MAX_VOCABULARY_TERM_CHARS: usize = 64;
+
+/// Trims, de-duplicates (case-insensitively) and bounds a user-supplied vocabulary list.
+pub fn normalize_vocabulary(terms: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for term in terms {
+        let term = term.split_whitespace().collect::<Vec<_>>().join(" ");
+        if term.is_empty() || term.chars().count() > MAX_VOCABULARY_TERM_CHARS {
+            continue;
+        }
+        if out.iter().any(|known| known.eq_ignore_ascii_case(&term)) {
+            continue;
+        }
+        out.push(term);
+        if out.len() == MAX_VOCABULARY_TERMS {
+            break;
+        }
+    }
+    out
+}
+
+fn glossary(terms: &[String]) -> String {
+    if terms.is_empty() {
+        String::new()
+    } else {
+        format!("{}.", terms.join(", "))
+    }
+}
+
+/// Smallest `i` in `0..len` for which `fits(i)` holds, or `len` if none does. `fits` must only
+/// ever turn from false to true as `i` grows, which lets this make O(log len) calls.
+fn first_fitting(len: usize, fits: impl Fn(usize) -> bool) -> usize {
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if fits(mid) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    low
+}
+
+/// Builds whisper's initial prompt from the vocabulary followed by the tail of the previous
+/// dictation, trimmed to `budget` tokens as measured by `count_tokens`. Vocabulary terms are
+/// kept in list order; previous text is cut from the front so the most recent words survive.
+/// Both cuts are found by binary search since token counts only shrink as text is removed.
+pub fn build_initial_prompt(
+    vocabulary: &[String],
+    previous_text: Option<&str>,
+    budget: usize,
+    count_tokens: impl Fn(&str) -> usize,
+) -> Option<String> {
+    let dropped_terms = first_fitting(vocabulary.len(), |dropped| {
+        count_tokens(&glossary(&vocabulary[..vocabulary.len() - dropped])) <= budget
+    });
+    let terms = &vocabulary[..vocabulary.len() - dropped_terms];
+    let glossary_text = glossary(terms);
+
+    let remaining = budget.saturating_sub(if terms.is_empty() {
+        0
+    } else {
+        count_tokens(&glossary_text)
+    });
+    let previous_words: Vec<&str> = previous_text
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect();
+    let start = first_fitting(previous_words.len(), |start| {
+        count_tokens(&previous_words[start..].join(" ")) <= remaining
+    });
+    let context = previous_words[start..].join(" ");
+
+    let prompt = [glossary_text, context]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!prompt.is_empty()).then_some(prompt)
+}
+
+/// Rough token estimate for engines without a local tokenizer (about 4 characters per token).
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::{build_initial_prompt, normalize_vocabulary};
+
+    fn words(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    #[test]
+    fn normalizes_vocabulary_terms() {
+        let terms = normalize_vocabulary(&[
+            " OpenSpeak ".to_string(),
+            "openspeak".to_string(),
+            "".to_string(),
+            "Kubernetes  operator".to_string(),
+        ]);
+        assert_eq!(terms, vec!["OpenSpeak", "Kubernetes operator"]);
+    }
+
+    #[test]
+    fn combines_vocabulary_and_previous_text() {
+        let vocabulary = vec!["OpenSpeak".to_string(), "Tauri".to_string()];
+        let prompt = build_initial_prompt(&vocabulary, Some("we shipped it"), 50, words);
+        assert_eq!(prompt.as_deref(), Some("OpenSpeak, Tauri. we shipped it"));
+        assert_eq!(build_initial_prompt(&[], None, 50, words), None);
+    }
+
+    #[test]
+    fn trims_previous_text_from_the_front_first() {
+        let vocabulary = vec!["OpenSpeak".to_string(), "Tauri".to_string()];
+        let prompt = build_initial_prompt(&vocabulary, Some("one two three four five"), 4, words);
+        assert_eq!(prompt.as_deref(), Some("OpenSpeak, Tauri. four five"));
+    }
+
+    #[test]
+    fn drops_trailing_vocabulary_terms_over_budget() {
+        let vocabulary = vec!["alpha".to_string(), "beta".to_string(), "gamma".to_string()];
+        let prompt = build_initial_prompt(&vocabulary, Some("context"), 2, words);
+        assert_eq!(prompt.as_deref(), Some("alpha, beta."));
+    }
+
+    #[test]
+    fn trims_long_previous_text_with_few_token_counts() {
+        let previous = (0..2_000)
+            .map(|i| format!("w{i}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let calls = Cell::new(0);
+        let prompt = build_initial_prompt(&[], Some(&previous), 3, |text| {
+            calls.set(calls.get() + 1);
+            words(text)
+        });
+        assert_eq!(prompt.as_deref(), Some("w1997 w1998 w1999"));
+        assert!(calls.get() <= 16);
+    }
+}
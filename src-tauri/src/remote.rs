@@ -0,0 +1,408 @@
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    audio::TARGET_SAMPLE_RATE,
+    prompt::{build_initial_prompt, estimate_tokens, PROMPT_TOKEN_BUDGET},
+    transcription::{
+        clean_whisper_text, InferenceOptions, TranscriptionCancelled, WhisperOutput, AUTO_LANGUAGE,
+    },
+    types::{RemoteEngineConfig, TranscriptSegment},
+};
+
+pub const API_KEY_ENV: &str = "OPENSPEAK_REMOTE_API_KEY";
+const KEYRING_SERVICE: &str = "OpenSpeak Remote Transcription";
+/// How often an in-flight request checks whether the transcription was cancelled.
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Deserialize)]
+struct RemoteResponse {
+    text: String,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    segments: Vec<RemoteSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteSegment {
+    start: f64,
+    end: f64,
+    text: String,
+    #[serde(default)]
+    avg_logprob: Option<f32>,
+    #[serde(default)]
+    no_speech_prob: Option<f32>,
+}
+
+pub fn is_valid_base_url(url: &str) -> bool {
+    let url = url.trim();
+    (url.starts_with("http://") || url.starts_with("https://"))
+        && !url.contains(char::is_whitespace)
+}
+
+/// `endpoint` is `transcriptions` or `translations`.
+fn audio_url(base_url: &str, endpoint: &str) -> String {
+    let base = base_url.trim().trim_end_matches('/');
+    if base.ends_with("/v1") {
+        format!("{base}/audio/{endpoint}")
+    } else {
+        format!("{base}/v1/audio/{endpoint}")
+    }
+}
+
+/// Reads the API key from the environment first, then from the OS credential store (macOS
+/// keychain, Windows Credential Manager or Secret Service; service "OpenSpeak Remote
+/// Transcription", account = base URL).
+fn api_key(base_url: &str) -> Option<String> {
+    if let Ok(key) = std::env::var(API_KEY_ENV) {
+        if !key.trim().is_empty() {
+            return Some(key.trim().to_string());
+        }
+    }
+    let stored = keyring::Entry::new(KEYRING_SERVICE, base_url.trim())
+        .and_then(|entry| entry.get_password());
+    match stored {
+        Ok(key) if !key.trim().is_empty() => Some(key.trim().to_string()),
+        Ok(_) | Err(keyring::Error::NoEntry) => None,
+        Err(err) => {
+            eprintln!("failed to read remote API key from the credential store: {err}");
+            None
+        }
+    }
+}
+
+/// Resolves once `options` is cancelled; the flag has no waker, so it is polled.
+async fn cancelled(options: &InferenceOptions) {
+    while !options.is_cancelled() {
+        tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+    }
+}
+
+/// Encodes 16 kHz mono samples as a 16-bit PCM WAV file.
+pub fn encode_wav(pcm: &[f32]) -> Vec<u8> {
+    let data_len = (pcm.len() * 2) as u32;
+    let byte_rate = TARGET_SAMPLE_RATE * 2;
+    let mut out = Vec::with_capacity(44 + data_len as usize);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    out.extend_from_slice(&1u16.to_le_bytes()); // mono
+    out.extend_from_slice(&TARGET_SAMPLE_RATE.to_le_bytes());
+    out.extend_from_slice(&byte_rate.to_le_bytes());
+    out.extend_from_slice(&2u16.to_le_bytes()); // block align
+    out.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    for sample in pcm {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+    out
+}
+
+fn multipart_body(boundary: &str, wav: &[u8], fields: &[(&str, &str)]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(wav.len() + 512);
+    body.extend_from_slice(
+        format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"audio.wav\"\r\nContent-Type: audio/wav\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(wav);
+    body.extend_from_slice(b"\r\n");
+    for (name, value) in fields {
+        body.extend_from_slice(
+            format!("--{boundary}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n")
+                .as_bytes(),
+        );
+    }
+    body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+    body
+}
+
+fn language_code(name: &str) -> Option<String> {
+    whisper_rs::get_lang_id(&name.to_lowercase())
+        .and_then(whisper_rs::get_lang_str)
+        .map(str::to_string)
+}
+
+fn into_output(response: RemoteResponse, options: &InferenceOptions) -> WhisperOutput {
+    let segments: Vec<TranscriptSegment> = response
+        .segments
+        .iter()
+        .filter_map(|segment| {
+            let text = clean_whisper_text(&segment.text);
+            if text.is_empty() {
+                return None;
+            }
+            // Servers that omit log probabilities are treated as fully confident.
+            let confidence = segment
+                .avg_logprob
+                .map_or(1.0, |logprob| logprob.exp().clamp(0.0, 1.0));
+            Some(TranscriptSegment {
+                text,
+                start_ms: (segment.start.max(0.0) * 1000.0) as u64,
+                end_ms: (segment.end.max(0.0) * 1000.0) as u64,
+                words: None,
+                confidence,
+                min_token_probability: confidence,
+                // Servers report their own no-speech probability; the energy measure is local only.
+                silent_ratio: 0.0,
+                no_speech_probability: segment.no_speech_prob,
+                speaker_turn_next: false,
+            })
+        })
+        .collect();
+    let confidence = if segments.is_empty() {
+        1.0
+    } else {
+        segments.iter().map(|s| s.confidence).sum::<f32>() / segments.len() as f32
+    };
+    let detected_language = if options.language == AUTO_LANGUAGE {
+        response.language.as_deref().and_then(language_code)
+    } else {
+        None
+    };
+
+    WhisperOutput {
+        text: clean_whisper_text(&response.text),
+        confidence,
+        segments,
+        detected_language,
+        language_probability: None,
+    }
+}
+
+/// Posts the capture to an OpenAI-compatible `/v1/audio/transcriptions` endpoint, or to
+/// `/v1/audio/translations` in translate mode.
+pub async fn transcribe_remote(
+    pcm: &[f32],
+    config: &RemoteEngineConfig,
+    options: &InferenceOptions,
+) -> Result<WhisperOutput> {
+    if pcm.is_empty() {
+        return Ok(WhisperOutput::empty());
+    }
+    if !is_valid_base_url(&config.base_url) {
+        bail!("remote transcription base URL is not configured");
+    }
+
+    let boundary = format!("openspeak-{}", Uuid::new_v4().simple());
+    let mut fields = vec![
+        ("model", config.model.as_str()),
+        ("response_format", "verbose_json"),
+    ];
+    // The translations endpoint always outputs English and takes no language hint.
+    if options.language != AUTO_LANGUAGE && !options.translate {
+        fields.push(("language", options.language.as_str()));
+    }
+    let prompt = build_initial_prompt(
+        &options.vocabulary,
+        options.previous_text.as_deref(),
+        PROMPT_TOKEN_BUDGET,
+        estimate_tokens,
+    );
+    if let Some(prompt) = &prompt {
+        fields.push(("prompt", prompt.as_str()));
+    }
+    let body = multipart_body(&boundary, &encode_wav(pcm), &fields);
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.timeout_secs.max(1)))
+        .build()
+        .context("failed to build HTTP client")?;
+    let mut request = client
+        .post(audio_url(
+            &config.base_url,
+            if options.translate {
+                "translations"
+            } else {
+                "transcriptions"
+            },
+        ))
+        .header(
+            reqwest::header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={boundary}"),
+        )
+        .body(body);
+    if let Some(key) = api_key(&config.base_url) {
+        request = request.bearer_auth(key);
+    }
+
+    let exchange = async {
+        request
+            .send()
+            .await
+            .context("failed to reach remote transcription server")?
+            .error_for_status()
+            .context("remote transcription failed with non-success status")?
+            .json::<RemoteResponse>()
+            .await
+            .context("failed to parse remote transcription response")
+    };
+    // Dropping the request future aborts the upload or the wait for the server's reply.
+    let response = tokio::select! {
+        biased;
+        _ = cancelled(options) => return Err(TranscriptionCancelled.into()),
+        response = exchange => response?,
+    };
+
+    Ok(into_output(response, options))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::atomic::Ordering,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::{encode_wav, transcribe_remote};
+    use crate::{
+        config::default_config,
+        transcription::{is_cancellation, InferenceOptions},
+        types::RemoteEngineConfig,
+    };
+
+    /// Accepts one request, returns its raw bytes through the join handle and replies with `body`.
+    fn stand_in_server(body: &'static str) -> (String, thread::JoinHandle<Vec<u8>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 8192];
+            loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= header_end + 4 + content_length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn encodes_wav_header_for_16khz_mono() {
+        let wav = encode_wav(&[0.0, 1.0, -1.0]);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..12], b"WAVE");
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16_000);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 6);
+        assert_eq!(wav.len(), 50);
+    }
+
+    #[tokio::test]
+    async fn posts_wav_to_stand_in_server() {
+        let (base_url, server) = stand_in_server(
+            r#"{"text":" hello from the lan ","language":"english","segments":[{"start":0.0,"end":1.5,"text":" hello from the lan","avg_logprob":-0.1,"no_speech_prob":0.01}]}"#,
+        );
+        let config = RemoteEngineConfig {
+            base_url,
+            model: "whisper-1".to_string(),
+            timeout_secs: 5,
+            fallback_to_local: false,
+        };
+        let options = InferenceOptions {
+            language: "auto".to_string(),
+            ..InferenceOptions::from_config(&default_config())
+        };
+
+        let output = transcribe_remote(&[0.0; 1600], &config, &options)
+            .await
+            .unwrap();
+        let request = String::from_utf8_lossy(&server.join().unwrap()).to_string();
+
+        assert!(request.starts_with("POST /v1/audio/transcriptions "));
+        assert!(request.contains("name=\"model\"\r\n\r\nwhisper-1"));
+        assert!(!request.contains("name=\"language\""));
+        assert_eq!(output.text, "hello from the lan");
+        assert_eq!(output.detected_language.as_deref(), Some("en"));
+        assert_eq!(output.segments[0].end_ms, 1_500);
+        assert!((output.confidence - (-0.1f32).exp()).abs() < 1e-5);
+    }
+
+    #[tokio::test]
+    async fn translate_mode_posts_to_translations_endpoint() {
+        let (base_url, server) = stand_in_server(r#"{"text":"good morning"}"#);
+        let config = RemoteEngineConfig {
+            base_url: format!("{base_url}/v1/"),
+            model: "whisper-1".to_string(),
+            timeout_secs: 5,
+            fallback_to_local: false,
+        };
+        let options = InferenceOptions {
+            language: "de".to_string(),
+            translate: true,
+            ..InferenceOptions::from_config(&default_config())
+        };
+
+        let output = transcribe_remote(&[0.0; 1600], &config, &options)
+            .await
+            .unwrap();
+        let request = String::from_utf8_lossy(&server.join().unwrap()).to_string();
+
+        assert!(request.starts_with("POST /v1/audio/translations "));
+        assert!(!request.contains("name=\"language\""));
+        assert_eq!(output.text, "good morning");
+    }
+
+    #[tokio::test]
+    async fn cancelling_aborts_a_pending_request() {
+        // Accepts the connection but never answers, like a stalled server.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || listener.accept().map(|(stream, _)| stream));
+        let config = RemoteEngineConfig {
+            base_url,
+            model: "whisper-1".to_string(),
+            timeout_secs: 30,
+            fallback_to_local: false,
+        };
+        let options = InferenceOptions::from_config(&default_config());
+        let cancel = options.cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            cancel.store(true, Ordering::Relaxed);
+        });
+
+        let started = Instant::now();
+        let err = transcribe_remote(&[0.0; 1600], &config, &options)
+            .await
+            .unwrap_err();
+        assert!(is_cancellation(&err));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        drop(server.join().unwrap());
+    }
+}
@@ -0,0 +1,212 @@
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+
+use crate::{
+    app_state::AppState,
+    audio::{AudioTap, TARGET_SAMPLE_RATE},
+    chunking::normalize_word,
+    filter::filter_text,
+    model::{active_model, is_model_installed, model_path},
+    overlay::emit_partial_transcript,
+    transcription::{run_inference, InferenceOptions},
+    types::{PartialTranscript, TranscriptWord},
+};
+
+const PREVIEW_STEP: Duration = Duration::from_millis(1000);
+/// Longest stretch of uncommitted audio transcribed in one preview pass.
+const PREVIEW_WINDOW_SAMPLES: usize = TARGET_SAMPLE_RATE as usize * 10;
+const PREVIEW_MIN_SAMPLES: usize = TARGET_SAMPLE_RATE as usize;
+/// Words ending this close to the end of the window may be cut off and are not committed.
+const EDGE_GUARD_MS: u64 = 1000;
+
+fn preview_settings(state: &AppState, session_id: Uuid) -> Option<(String, InferenceOptions)> {
+    state.with_lock(|s| {
+        let active = s
+            .active_session
+            .as_ref()
+            .is_some_and(|session| session.id == session_id);
+        active.then(|| {
+            let mut options = InferenceOptions::from_config(&s.config);
+            // Word timings tell how far the window can move past committed text.
+            options.word_timestamps = true;
+            (active_model(&s.config), options)
+        })
+    })
+}
+
+/// Number of leading words of `current` that are safe to commit. While the window is still
+/// growing a word must also have been decoded the same way by the previous pass; a full window
+/// is not re-run on more audio, so its words only need to end clear of the cut.
+fn stable_word_count(
+    previous: &[TranscriptWord],
+    current: &[TranscriptWord],
+    window_ms: u64,
+    window_full: bool,
+) -> usize {
+    let agreed = if window_full {
+        current.len()
+    } else {
+        previous
+            .iter()
+            .zip(current)
+            .take_while(|(before, now)| normalize_word(&before.text) == normalize_word(&now.text))
+            .count()
+    };
+    current[..agreed]
+        .iter()
+        .take_while(|word| word.end_ms + EDGE_GUARD_MS <= window_ms)
+        .count()
+}
+
+fn join_words(words: &[TranscriptWord]) -> String {
+    words
+        .iter()
+        .map(|word| word.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Transcribes the live capture over a sliding window and emits `partial-transcript` events
+/// until the session stops. Words that two consecutive passes agree on are committed and the
+/// window restarts after the last of them, so a word cut by the window edge is re-decoded
+/// whole in the next pass. Once the session stops, `jobs::run_job` transcribes the full
+/// capture and emits it as the final transcript, replacing the preview entirely.
+pub fn spawn_partial_transcriber(app: AppHandle, session_id: Uuid, tap: AudioTap) {
+    tauri::async_runtime::spawn(async move {
+        let mut committed_text = String::new();
+        let mut committed_samples = 0usize;
+        let mut previous_words: Vec<TranscriptWord> = Vec::new();
+
+        loop {
+            tokio::time::sleep(PREVIEW_STEP).await;
+
+            let state = app.state::<AppState>();
+            let Some((model, options)) = preview_settings(&state, session_id) else {
+                break;
+            };
+            // Previews never trigger a download; the final pass takes care of that.
+            if !is_model_installed(&model) {
+                break;
+            }
+            let Ok(path) = model_path(&model) else {
+                break;
+            };
+
+            let available = tap.available_samples();
+            if available.saturating_sub(committed_samples) < PREVIEW_MIN_SAMPLES {
+                continue;
+            }
+            let window_len = (available - committed_samples).min(PREVIEW_WINDOW_SAMPLES);
+            let mut pcm = tap.pcm_from(committed_samples);
+            pcm.truncate(window_len);
+
+            let filter_config = options.filter.clone();
+            let app_for_inference = app.clone();
+            let inference = tauri::async_runtime::spawn_blocking(move || {
+                let state = app_for_inference.state::<AppState>();
+                let ctx = state.contexts.get_or_load(&model, &path)?;
+                run_inference(&pcm, &ctx, &options)
+            })
+            .await;
+            let Ok(Ok(output)) = inference else {
+                continue;
+            };
+            let clean = |text: String| {
+                if filter_config.enabled {
+                    filter_text(&text, &filter_config)
+                } else {
+                    text
+                }
+            };
+
+            let mut words: Vec<TranscriptWord> = output
+                .segments
+                .into_iter()
+                .flat_map(|segment| segment.words.unwrap_or_default())
+                .collect();
+            let window_ms = (window_len * 1000 / TARGET_SAMPLE_RATE as usize) as u64;
+            let window_full = window_len >= PREVIEW_WINDOW_SAMPLES;
+            let stable = stable_word_count(&previous_words, &words, window_ms, window_full);
+            let advance_ms = if stable > 0 {
+                let text = clean(join_words(&words[..stable]));
+                if !text.is_empty() {
+                    if !committed_text.is_empty() {
+                        committed_text.push(' ');
+                    }
+                    committed_text.push_str(&text);
+                }
+                words[stable - 1].end_ms
+            } else if window_full {
+                // Nothing usable in a whole window (silence or noise); skip all but the edge.
+                window_ms - EDGE_GUARD_MS
+            } else {
+                0
+            };
+            committed_samples += (advance_ms * u64::from(TARGET_SAMPLE_RATE) / 1000) as usize;
+            previous_words = if advance_ms > 0 && stable == 0 {
+                Vec::new()
+            } else {
+                words.split_off(stable)
+            };
+            let tentative_text = clean(join_words(&previous_words));
+
+            if preview_settings(&state, session_id).is_none() {
+                break;
+            }
+            emit_partial_transcript(
+                &app,
+                &PartialTranscript {
+                    session_id: session_id.to_string(),
+                    committed_text: committed_text.clone(),
+                    tentative_text,
+                    is_final: false,
+                },
+            );
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::stable_word_count;
+    use crate::types::TranscriptWord;
+
+    fn words(timed: &[(&str, u64, u64)]) -> Vec<TranscriptWord> {
+        timed
+            .iter()
+            .map(|&(text, start_ms, end_ms)| TranscriptWord {
+                text: text.to_string(),
+                start_ms,
+                end_ms,
+                probability: 0.9,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn commits_only_words_two_passes_agree_on() {
+        let previous = words(&[("send", 0, 400), ("the", 400, 600), ("rep", 600, 2_000)]);
+        let current = words(&[
+            ("Send", 0, 400),
+            ("the", 400, 600),
+            ("report", 600, 1_200),
+            ("today", 1_300, 2_800),
+        ]);
+        // "report" differs from the cut-off "rep" of the previous pass, so it waits.
+        assert_eq!(stable_word_count(&previous, &current, 3_000, false), 2);
+        // Agreed words near the window edge wait for more audio too.
+        assert_eq!(stable_word_count(&current, &current, 3_000, false), 3);
+    }
+
+    #[test]
+    fn full_window_commits_everything_clear_of_the_edge() {
+        let current = words(&[
+            ("one", 0, 4_000),
+            ("two", 4_000, 8_500),
+            ("thr", 9_500, 10_000),
+        ]);
+        assert_eq!(stable_word_count(&[], &current, 10_000, true), 2);
+    }
+}
@@ -1,15 +1,285 @@
-use anyhow::{Context, Result};
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use std::{
+    ffi::c_void,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
-use crate::model::{download_model, ensure_model_async, model_path};
+use anyhow::{anyhow, bail, Context, Result};
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperState};
+
+use crate::{
+    audio::{silent_frame_ratio, TARGET_SAMPLE_RATE},
+    chunking::{merge_chunk_outputs, plan_chunks},
+    context_cache::WhisperContextCache,
+    model::{
+        download_model, ensure_model_async, mark_model_used, model_path, supports_speaker_turns,
+        verify_model,
+    },
+    prompt::{build_initial_prompt, PROMPT_TOKEN_BUDGET},
+    types::{
+        AppConfig, DecodingConfig, DownloadConfig, OutputFilterConfig, TranscriptSegment,
+        TranscriptWord,
+    },
+};
+
+pub const AUTO_LANGUAGE: &str = "auto";
+pub const MAX_CHUNK_WORKERS: usize = 4;
+pub const GREEDY_STRATEGY: &str = "greedy";
+pub const BEAM_SEARCH_STRATEGY: &str = "beam-search";
+pub const TRANSCRIBE_TASK: &str = "transcribe";
+pub const TRANSLATE_TASK: &str = "translate";
+const MAX_THREADS: usize = 64;
+const MAX_BEAM_SIZE: u32 = 16;
+/// Whisper segment timestamps are reported in 10 ms units.
+const SAMPLES_PER_TIMESTAMP_UNIT: i64 = TARGET_SAMPLE_RATE as i64 / 100;
+const LOW_CONFIDENCE_THRESHOLD: f32 = 0.5;
+const LOW_SEGMENT_CONFIDENCE_THRESHOLD: f32 = 0.35;
 
 #[derive(Debug, Clone)]
 pub struct WhisperOutput {
     pub text: String,
     pub confidence: f32,
+    pub segments: Vec<TranscriptSegment>,
+    pub detected_language: Option<String>,
+    pub language_probability: Option<f32>,
+}
+
+impl WhisperOutput {
+    pub fn empty() -> Self {
+        Self {
+            text: String::new(),
+            confidence: 0.0,
+            segments: Vec::new(),
+            detected_language: None,
+            language_probability: None,
+        }
+    }
+}
+
+/// Per-dictation settings taken from the config when a transcription starts.
+#[derive(Debug, Clone)]
+pub struct InferenceOptions {
+    pub language: String,
+    /// Translate speech into English instead of transcribing it in the spoken language.
+    pub translate: bool,
+    /// Decode tinydiarize speaker-turn tokens; only meaningful for `-tdrz` models.
+    pub speaker_turns: bool,
+    pub word_timestamps: bool,
+    pub threads: usize,
+    pub decoding: DecodingConfig,
+    /// Number of long-audio chunks decoded concurrently, each on its own whisper state.
+    pub chunk_workers: usize,
+    pub filter: OutputFilterConfig,
+    pub vocabulary: Vec<String>,
+    /// Text of the previous dictation, passed as context when carry-over is enabled.
+    pub previous_text: Option<String>,
+    /// Set from `cancel_recording` to abort a running transcription.
+    pub cancel: Arc<AtomicBool>,
+}
+
+impl InferenceOptions {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            language: config.language.clone(),
+            translate: config.task == TRANSLATE_TASK,
+            speaker_turns: supports_speaker_turns(&config.model_default),
+            word_timestamps: config.word_timestamps,
+            threads: config
+                .decoding
+                .threads
+                .unwrap_or_else(available_threads)
+                .clamp(1, MAX_THREADS),
+            decoding: config.decoding.clone(),
+            chunk_workers: config.chunk_workers.clamp(1, MAX_CHUNK_WORKERS),
+            filter: config.output_filter.clone(),
+            vocabulary: config.vocabulary.clone(),
+            previous_text: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("transcription cancelled")]
+pub struct TranscriptionCancelled;
+
+pub fn is_cancellation(err: &anyhow::Error) -> bool {
+    err.is::<TranscriptionCancelled>()
+}
+
+/// A text token with its timing in whisper's 10 ms units.
+struct TimedToken {
+    text: String,
+    t0: i64,
+    t1: i64,
+    p: f32,
+}
+
+fn available_threads() -> usize {
+    std::thread::available_parallelism().map_or(4, |n| n.get())
+}
+
+pub fn validate_decoding(decoding: &DecodingConfig) -> Result<()> {
+    match decoding.strategy.as_str() {
+        GREEDY_STRATEGY => {
+            if decoding.best_of == 0 {
+                bail!("best-of must be at least 1");
+            }
+        }
+        BEAM_SEARCH_STRATEGY => {
+            if !(1..=MAX_BEAM_SIZE).contains(&decoding.beam_size) {
+                bail!("beam size must be between 1 and {MAX_BEAM_SIZE}");
+            }
+        }
+        other => bail!("unknown decoding strategy: {other}"),
+    }
+    if !(0.0..=1.0).contains(&decoding.temperature) {
+        bail!("temperature must be between 0 and 1");
+    }
+    if !(0.0..=1.0).contains(&decoding.temperature_increment) {
+        bail!("temperature increment must be between 0 and 1");
+    }
+    if !decoding.entropy_threshold.is_finite() || decoding.entropy_threshold <= 0.0 {
+        bail!("entropy threshold must be a positive number");
+    }
+    if !decoding.logprob_threshold.is_finite() || decoding.logprob_threshold > 0.0 {
+        bail!("log-probability threshold must be zero or negative");
+    }
+    if let Some(threads) = decoding.threads {
+        if !(1..=MAX_THREADS).contains(&threads) {
+            bail!("thread count must be between 1 and {MAX_THREADS}");
+        }
+    }
+    Ok(())
+}
+
+fn sampling_strategy(decoding: &DecodingConfig) -> SamplingStrategy {
+    if decoding.strategy == BEAM_SEARCH_STRATEGY {
+        SamplingStrategy::BeamSearch {
+            beam_size: decoding.beam_size.max(1) as i32,
+            // whisper.cpp ignores patience; -1.0 is its default.
+            patience: -1.0,
+        }
+    } else {
+        SamplingStrategy::Greedy {
+            best_of: decoding.best_of.max(1) as i32,
+        }
+    }
+}
+
+pub fn is_supported_task(task: &str) -> bool {
+    matches!(task, TRANSCRIBE_TASK | TRANSLATE_TASK)
+}
+
+/// Accepts `auto` or any language code known to whisper (e.g. `en`, `de`, `pt`).
+pub fn is_supported_language(code: &str) -> bool {
+    if code == AUTO_LANGUAGE {
+        return true;
+    }
+    whisper_rs::get_lang_id(code)
+        .and_then(whisper_rs::get_lang_str)
+        .is_some_and(|canonical| canonical == code)
+}
+
+/// Token-count weighted mean of the segment confidences, so short filler segments do not
+/// dominate a long dictation.
+fn overall_confidence(segments: &[TranscriptSegment], token_counts: &[usize]) -> f32 {
+    let total_tokens: usize = token_counts.iter().sum();
+    if total_tokens == 0 {
+        return 0.0;
+    }
+    let weighted: f32 = segments
+        .iter()
+        .zip(token_counts)
+        .map(|(segment, count)| segment.confidence * *count as f32)
+        .sum();
+    weighted / total_tokens as f32
+}
+
+/// Flags dictations that should be reviewed before they are pasted anywhere.
+pub fn is_low_confidence(output: &WhisperOutput) -> bool {
+    if output.text.is_empty() {
+        return false;
+    }
+    output.confidence < LOW_CONFIDENCE_THRESHOLD
+        || output
+            .segments
+            .iter()
+            .any(|segment| segment.confidence < LOW_SEGMENT_CONFIDENCE_THRESHOLD)
+}
+
+fn timestamp_to_ms(t: i64) -> u64 {
+    t.max(0) as u64 * 10
+}
+
+/// Joins sub-word tokens into words: a token starting with whitespace opens a new word.
+fn group_words(tokens: &[TimedToken]) -> Vec<TranscriptWord> {
+    let mut words: Vec<TranscriptWord> = Vec::new();
+    let mut token_counts: Vec<usize> = Vec::new();
+    for token in tokens {
+        let starts_word = token.text.starts_with(char::is_whitespace) || words.is_empty();
+        let piece = token.text.trim();
+        if starts_word {
+            if piece.is_empty() {
+                continue;
+            }
+            words.push(TranscriptWord {
+                text: piece.to_string(),
+                start_ms: timestamp_to_ms(token.t0),
+                end_ms: timestamp_to_ms(token.t1),
+                probability: token.p,
+            });
+            token_counts.push(1);
+        } else if let (Some(word), Some(count)) = (words.last_mut(), token_counts.last_mut()) {
+            word.text.push_str(piece);
+            word.end_ms = word.end_ms.max(timestamp_to_ms(token.t1));
+            word.probability = (word.probability * *count as f32 + token.p) / (*count + 1) as f32;
+            *count += 1;
+        }
+    }
+    words
+}
+
+fn segment_samples(pcm: &[f32], t0: i64, t1: i64) -> &[f32] {
+    let start = (t0.max(0) * SAMPLES_PER_TIMESTAMP_UNIT) as usize;
+    let end = (t1.max(0) * SAMPLES_PER_TIMESTAMP_UNIT) as usize;
+    let end = end.min(pcm.len());
+    &pcm[start.min(end)..end]
 }
 
-fn clean_whisper_text(input: &str) -> String {
+fn detect_language(state: &mut WhisperState, pcm: &[f32], threads: usize) -> Result<(String, f32)> {
+    state
+        .pcm_to_mel(pcm, threads)
+        .context("failed to compute mel spectrogram for language detection")?;
+    let probs = state
+        .lang_detect(0, threads)
+        .context("whisper language detection failed")?;
+    let (lang_id, probability) = probs
+        .iter()
+        .copied()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .context("whisper returned no language probabilities")?;
+    let code = whisper_rs::get_lang_str(lang_id as i32)
+        .context("whisper detected an unknown language id")?;
+    Ok((code.to_string(), probability))
+}
+
+/// whisper.cpp abort callback; `user_data` points at the `AtomicBool` behind
+/// `InferenceOptions::cancel`.
+unsafe extern "C" fn abort_on_cancel(user_data: *mut c_void) -> bool {
+    // SAFETY: `run_inference` passes a pointer to the cancel flag, which outlives `full()`.
+    unsafe { (*(user_data as *const AtomicBool)).load(Ordering::Relaxed) }
+}
+
+pub fn clean_whisper_text(input: &str) -> String {
     let mut out = Vec::new();
     for token in input.split_whitespace() {
         if token.eq_ignore_ascii_case("[BLANK_AUDIO]") {
@@ -20,71 +290,368 @@ fn clean_whisper_text(input: &str) -> String {
     out.join(" ").trim().to_string()
 }
 
-fn run_inference(pcm: &[f32], model_path: &str) -> Result<WhisperOutput> {
-    let ctx = WhisperContext::new_with_params(model_path, WhisperContextParameters::default())
-        .context("failed to initialize whisper context")?;
+pub fn run_inference(
+    pcm: &[f32],
+    ctx: &WhisperContext,
+    options: &InferenceOptions,
+) -> Result<WhisperOutput> {
+    if options.is_cancelled() {
+        return Err(TranscriptionCancelled.into());
+    }
+    if options.translate && !ctx.is_multilingual() {
+        bail!(
+            "the selected model is English-only and cannot translate; choose a multilingual model"
+        );
+    }
     let mut state = ctx
         .create_state()
         .context("failed to create whisper state")?;
 
-    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
-    params.set_language(Some("en"));
-    params.set_translate(false);
+    let detection = if options.language == AUTO_LANGUAGE {
+        Some(detect_language(&mut state, pcm, options.threads)?)
+    } else {
+        None
+    };
+    let language = detection
+        .as_ref()
+        .map_or(options.language.as_str(), |(code, _)| code.as_str());
+
+    let initial_prompt = build_initial_prompt(
+        &options.vocabulary,
+        options.previous_text.as_deref(),
+        PROMPT_TOKEN_BUDGET,
+        |text| {
+            ctx.tokenize(text, text.len() + 1)
+                .map_or(usize::MAX, |tokens| tokens.len())
+        },
+    );
+
+    let mut params = FullParams::new(sampling_strategy(&options.decoding));
+    if let Some(prompt) = &initial_prompt {
+        params.set_initial_prompt(prompt);
+    }
+    params.set_language(Some(language));
+    params.set_translate(options.translate);
     params.set_print_special(false);
     params.set_print_progress(false);
     params.set_print_realtime(false);
     params.set_print_timestamps(false);
-    params.set_n_threads(4);
+    params.set_token_timestamps(options.word_timestamps);
+    params.set_tdrz_enable(options.speaker_turns);
+    params.set_n_threads(options.threads.max(1) as i32);
+    params.set_temperature(options.decoding.temperature);
+    params.set_temperature_inc(options.decoding.temperature_increment);
+    params.set_entropy_thold(options.decoding.entropy_threshold);
+    params.set_logprob_thold(options.decoding.logprob_threshold);
+    // `set_abort_callback_safe` hands whisper.cpp a boxed closure its trampoline reads as a
+    // different type, and never frees it. The flag is borrowed for the whole `full()` call, so
+    // it can be passed directly with nothing to allocate or free.
+    // SAFETY: `abort_on_cancel` only reads the `AtomicBool` that `options.cancel` keeps alive.
+    unsafe {
+        params.set_abort_callback(Some(abort_on_cancel));
+        params.set_abort_callback_user_data(Arc::as_ptr(&options.cancel) as *mut c_void);
+    }
 
-    state
-        .full(params, pcm)
-        .context("whisper inference failed")?;
+    let inference = state.full(params, pcm);
+    if options.is_cancelled() {
+        return Err(TranscriptionCancelled.into());
+    }
+    inference.context("whisper inference failed")?;
 
     let num_segments = state
         .full_n_segments()
         .context("failed to read whisper segments")?;
+    let token_eot = ctx.token_eot();
     let mut text = String::new();
+    let mut segments = Vec::new();
+    let mut token_counts = Vec::new();
     for i in 0..num_segments {
-        let segment = state
+        let segment_text = state
             .full_get_segment_text(i)
             .context("failed to read whisper segment text")?;
-        text.push_str(segment.trim());
+        let segment_text = clean_whisper_text(&segment_text);
+        if segment_text.is_empty() {
+            continue;
+        }
+
+        let n_tokens = state
+            .full_n_tokens(i)
+            .context("failed to read whisper token count")?;
+        let mut probs = Vec::with_capacity(n_tokens.max(0) as usize);
+        let mut timed_tokens = Vec::new();
+        for j in 0..n_tokens {
+            let token = state
+                .full_get_token_data(i, j)
+                .context("failed to read whisper token data")?;
+            // Special tokens (timestamps, end-of-text, ...) say nothing about recognition quality.
+            if token.id >= token_eot {
+                continue;
+            }
+            probs.push(token.p);
+            if options.word_timestamps {
+                // Tokens holding part of a multi-byte character are not valid UTF-8 on their
+                // own; they still extend the current word's timing.
+                let token_text = state.full_get_token_text(i, j).unwrap_or_default();
+                timed_tokens.push(TimedToken {
+                    text: token_text,
+                    t0: token.t0,
+                    t1: token.t1,
+                    p: token.p,
+                });
+            }
+        }
+        let t0 = state
+            .full_get_segment_t0(i)
+            .context("failed to read whisper segment start")?;
+        let t1 = state
+            .full_get_segment_t1(i)
+            .context("failed to read whisper segment end")?;
+
+        let confidence = if probs.is_empty() {
+            0.0
+        } else {
+            probs.iter().sum::<f32>() / probs.len() as f32
+        };
+        let min_token_probability = probs.iter().copied().reduce(f32::min).unwrap_or(0.0);
+
+        let speaker_turn_next =
+            options.speaker_turns && state.full_get_segment_speaker_turn_next(i);
+
+        text.push_str(&segment_text);
         text.push(' ');
+        token_counts.push(probs.len());
+        segments.push(TranscriptSegment {
+            text: segment_text,
+            start_ms: timestamp_to_ms(t0),
+            end_ms: timestamp_to_ms(t1),
+            words: options.word_timestamps.then(|| group_words(&timed_tokens)),
+            confidence,
+            min_token_probability,
+            silent_ratio: silent_frame_ratio(segment_samples(pcm, t0, t1)),
+            // whisper.cpp does not expose the no-speech token probability.
+            no_speech_probability: None,
+            speaker_turn_next,
+        });
     }
 
+    let (detected_language, language_probability) = match detection {
+        Some((code, probability)) => (Some(code), Some(probability)),
+        None => (None, None),
+    };
+
     Ok(WhisperOutput {
         text: clean_whisper_text(&text),
-        confidence: 0.9,
+        confidence: overall_confidence(&segments, &token_counts),
+        segments,
+        detected_language,
+        language_probability,
     })
 }
 
-pub async fn transcribe_locally(pcm: Vec<f32>, model: &str) -> Result<WhisperOutput> {
+/// Runs whisper over a capture of any length, splitting recordings longer than one whisper
+/// window into overlapping chunks and merging the results.
+pub fn run_chunked_inference(
+    pcm: &[f32],
+    ctx: &WhisperContext,
+    options: &InferenceOptions,
+) -> Result<WhisperOutput> {
+    let chunks = plan_chunks(pcm);
+    if chunks.len() == 1 {
+        return run_inference(pcm, ctx, options);
+    }
+
+    // Detect once up front so every chunk decodes in the same language.
+    let mut options = options.clone();
+    let mut detection = None;
+    if options.language == AUTO_LANGUAGE {
+        let mut state = ctx
+            .create_state()
+            .context("failed to create whisper state")?;
+        let (code, probability) =
+            detect_language(&mut state, &pcm[chunks[0].clone()], options.threads)?;
+        options.language = code.clone();
+        detection = Some((code, probability));
+    }
+
+    let workers = options.chunk_workers.clamp(1, MAX_CHUNK_WORKERS);
+    let mut outputs = Vec::with_capacity(chunks.len());
+    if workers == 1 {
+        for chunk in &chunks {
+            outputs.push(run_inference(&pcm[chunk.clone()], ctx, &options)?);
+        }
+    } else {
+        options.threads = (options.threads / workers).max(1);
+        for batch in chunks.chunks(workers) {
+            let results: Vec<Result<WhisperOutput>> = std::thread::scope(|scope| {
+                let handles: Vec<_> = batch
+                    .iter()
+                    .map(|chunk| {
+                        let options = &options;
+                        scope.spawn(move || run_inference(&pcm[chunk.clone()], ctx, options))
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| {
+                        handle
+                            .join()
+                            .unwrap_or_else(|_| Err(anyhow!("chunk transcription panicked")))
+                    })
+                    .collect()
+            });
+            for result in results {
+                outputs.push(result?);
+            }
+        }
+    }
+
+    let mut merged = merge_chunk_outputs(&chunks, outputs);
+    if let Some((code, probability)) = detection {
+        merged.detected_language = Some(code);
+        merged.language_probability = Some(probability);
+    }
+    Ok(merged)
+}
+
+/// Loads the model and runs inference on a blocking thread, keeping the async runtime free.
+async fn infer_blocking(
+    contexts: &Arc<WhisperContextCache>,
+    model: &str,
+    path: &Path,
+    pcm: &Arc<Vec<f32>>,
+    options: &InferenceOptions,
+) -> Result<WhisperOutput> {
+    let contexts = Arc::clone(contexts);
+    let model = model.to_string();
+    let path = path.to_path_buf();
+    let pcm = Arc::clone(pcm);
+    let options = options.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let ctx = contexts.get_or_load(&model, &path)?;
+        run_chunked_inference(&pcm, &ctx, &options)
+    })
+    .await
+    .context("transcription task failed")?
+}
+
+pub async fn transcribe_locally(
+    pcm: Vec<f32>,
+    model: &str,
+    download: &DownloadConfig,
+    options: &InferenceOptions,
+    contexts: &Arc<WhisperContextCache>,
+) -> Result<WhisperOutput> {
     if pcm.is_empty() {
-        return Ok(WhisperOutput {
-            text: String::new(),
-            confidence: 0.0,
-        });
+        return Ok(WhisperOutput::empty());
     }
+    let pcm = Arc::new(pcm);
 
-    let initial_model_path = ensure_model_async(model).await?;
-    let initial_model_path = initial_model_path
-        .to_str()
-        .context("invalid model path for whisper runtime")?;
+    let initial_model_path = ensure_model_async(model, download).await?;
+    let first_attempt = infer_blocking(contexts, model, &initial_model_path, &pcm, options).await;
 
-    match run_inference(&pcm, initial_model_path) {
-        Ok(output) => Ok(output),
+    match first_attempt {
+        Ok(output) => {
+            mark_model_used(model);
+            Ok(output)
+        }
+        Err(first_error) if is_cancellation(&first_error) => Err(first_error),
         Err(first_error) => {
-            // If the model is corrupted/incomplete, refresh it once and retry.
+            // Only a model that fails verification is worth a (possibly multi-GB) refresh;
+            // anything else is reported as is.
+            let verification = verify_model(model).await?;
+            if verification.status.is_usable() {
+                return Err(first_error);
+            }
+            contexts.evict(model);
             if let Ok(path) = model_path(model) {
                 let _ = std::fs::remove_file(path);
             }
-            download_model(model).await?;
-            let refreshed = ensure_model_async(model).await?;
-            let refreshed = refreshed
-                .to_str()
-                .context("invalid refreshed model path for whisper runtime")?;
-            run_inference(&pcm, refreshed)
+            download_model(model, download, |_| {}).await?;
+            let refreshed = ensure_model_async(model, download).await?;
+            infer_blocking(contexts, model, &refreshed, &pcm, options)
+                .await
                 .with_context(|| format!("transcription failed after model refresh: {first_error}"))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        group_words, is_low_confidence, overall_confidence, validate_decoding, TimedToken,
+        WhisperOutput, BEAM_SEARCH_STRATEGY,
+    };
+    use crate::types::{DecodingConfig, TranscriptSegment};
+
+    fn segment(text: &str, confidence: f32) -> TranscriptSegment {
+        TranscriptSegment {
+            text: text.to_string(),
+            start_ms: 0,
+            end_ms: 0,
+            words: None,
+            confidence,
+            min_token_probability: confidence,
+            silent_ratio: 0.0,
+            no_speech_probability: None,
+            speaker_turn_next: false,
+        }
+    }
+
+    #[test]
+    fn weights_confidence_by_token_count() {
+        let segments = vec![segment("long segment", 0.9), segment("uh", 0.3)];
+        let confidence = overall_confidence(&segments, &[9, 1]);
+        assert!((confidence - 0.84).abs() < 1e-5);
+    }
+
+    #[test]
+    fn flags_single_weak_segment() {
+        let mut output = WhisperOutput::empty();
+        output.text = "hello there".to_string();
+        output.segments = vec![segment("hello", 0.95), segment("there", 0.2)];
+        output.confidence = 0.8;
+        assert!(is_low_confidence(&output));
+
+        output.segments[1].confidence = 0.7;
+        assert!(!is_low_confidence(&output));
+    }
+
+    #[test]
+    fn groups_subword_tokens_into_words() {
+        let token = |text: &str, t0: i64, t1: i64, p: f32| TimedToken {
+            text: text.to_string(),
+            t0,
+            t1,
+            p,
+        };
+        let words = group_words(&[
+            token(" Open", 0, 20, 0.9),
+            token("Speak", 20, 45, 0.7),
+            token(" works", 50, 80, 0.8),
+        ]);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "OpenSpeak");
+        assert_eq!((words[0].start_ms, words[0].end_ms), (0, 450));
+        assert!((words[0].probability - 0.8).abs() < 1e-5);
+        assert_eq!(words[1].text, "works");
+        assert_eq!((words[1].start_ms, words[1].end_ms), (500, 800));
+    }
+
+    #[test]
+    fn validates_decoding_settings() {
+        let mut decoding = DecodingConfig::default();
+        assert!(validate_decoding(&decoding).is_ok());
+
+        decoding.strategy = BEAM_SEARCH_STRATEGY.to_string();
+        decoding.beam_size = 0;
+        assert!(validate_decoding(&decoding).is_err());
+
+        decoding.beam_size = 5;
+        decoding.logprob_threshold = 0.5;
+        assert!(validate_decoding(&decoding).is_err());
+
+        decoding.logprob_threshold = -1.0;
+        decoding.threads = Some(0);
+        assert!(validate_decoding(&decoding).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,15 +27,427 @@ pub struct PrivacyConfig {
     pub persist_audio_debug: bool,
 }
 
+/// Settings for an OpenAI-compatible transcription server, e.g. a shared whisper box on the LAN.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteEngineConfig {
+    pub base_url: String,
+    pub model: String,
+    pub timeout_secs: u64,
+    pub fallback_to_local: bool,
+}
+
+impl Default for RemoteEngineConfig {
+    fn default() -> Self {
+        Self {
+            base_url: String::new(),
+            model: "whisper-1".to_string(),
+            timeout_secs: 30,
+            fallback_to_local: true,
+        }
+    }
+}
+
+/// Network settings applied to every model download.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadConfig {
+    /// Replaces `https://huggingface.co` for built-in models, keeping the repository path.
+    #[serde(default)]
+    pub mirror_base_url: String,
+    /// Full download URLs for individual model ids; these win over the mirror.
+    #[serde(default)]
+    pub url_overrides: BTreeMap<String, String>,
+    /// HTTP(S) proxy such as `http://proxy.example:3128`; empty uses the proxy environment.
+    #[serde(default)]
+    pub proxy_url: String,
+    /// PEM files with extra trusted root certificates, for proxies that intercept TLS.
+    #[serde(default)]
+    pub ca_cert_paths: Vec<String>,
+}
+
+/// Post-inference cleanup of annotations, hallucinated phrases and repetition loops.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputFilterConfig {
+    pub enabled: bool,
+    pub strip_annotations: bool,
+    pub drop_stock_phrases: bool,
+    /// Back-to-back repeats of a phrase beyond this count are collapsed; 0 disables.
+    pub max_phrase_repeats: usize,
+    /// Segments whose no-speech probability reaches this value are dropped, and the whole output
+    /// when they cover most of it. Local whisper does not report the probability, so this only
+    /// applies to the remote engine.
+    pub no_speech_threshold: f32,
+}
+
+impl Default for OutputFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            strip_annotations: true,
+            drop_stock_phrases: true,
+            max_phrase_repeats: 3,
+            no_speech_threshold: 0.9,
+        }
+    }
+}
+
+/// whisper.cpp decoding settings; the defaults match whisper.cpp's own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodingConfig {
+    /// `greedy` or `beam-search`.
+    pub strategy: String,
+    pub best_of: u32,
+    pub beam_size: u32,
+    pub temperature: f32,
+    /// Added to the temperature on each fallback retry; 0 disables fallback.
+    pub temperature_increment: f32,
+    pub entropy_threshold: f32,
+    pub logprob_threshold: f32,
+    /// Inference threads; `None` uses every available core.
+    pub threads: Option<usize>,
+}
+
+impl Default for DecodingConfig {
+    fn default() -> Self {
+        Self {
+            strategy: "greedy".to_string(),
+            best_of: 1,
+            beam_size: 5,
+            temperature: 0.0,
+            temperature_increment: 0.2,
+            entropy_threshold: 2.4,
+            logprob_threshold: -1.0,
+            threads: None,
+        }
+    }
+}
+
+/// Which build of `model_default` to run; unset fields keep that size's default build.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelVariant {
+    /// "f16", "q5_0", "q5_1" or "q8_0".
+    #[serde(default)]
+    pub quantization: Option<String>,
+    #[serde(default)]
+    pub english_only: Option<bool>,
+}
+
+/// The hardware-based model pick made on first launch, kept so settings can explain it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelRecommendation {
+    pub model_id: String,
+    pub quantization: Option<String>,
+    pub reason: String,
+    pub cpu_cores: usize,
+    pub simd_features: Vec<String>,
+    pub total_memory_bytes: u64,
+}
+
+fn default_cancel_hotkey() -> String {
+    "CommandOrControl+Shift+Backspace".to_string()
+}
+
+fn default_chunk_workers() -> usize {
+    1
+}
+
+fn default_task() -> String {
+    crate::transcription::TRANSCRIBE_TASK.to_string()
+}
+
+fn default_engine() -> String {
+    crate::engine::WHISPER_ENGINE.to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AppConfig {
     pub hotkey: String,
+    /// Discards the active recording or aborts a running transcription; empty disables it.
+    #[serde(default = "default_cancel_hotkey")]
+    pub cancel_hotkey: String,
     pub model_default: String,
+    #[serde(default)]
+    pub model_variant: ModelVariant,
+    #[serde(default)]
+    pub model_recommendation: Option<ModelRecommendation>,
+    /// Set once the user picks a model; the recommendation never overrides it afterwards.
+    #[serde(default)]
+    pub model_selected_by_user: bool,
     pub command_mode: String,
     pub paste_mode: String,
     pub language: String,
     pub privacy: PrivacyConfig,
+    #[serde(default)]
+    pub model_unload_after_secs: Option<u64>,
+    #[serde(default)]
+    pub word_timestamps: bool,
+    #[serde(default)]
+    pub streaming_preview: bool,
+    #[serde(default = "default_engine")]
+    pub engine: String,
+    #[serde(default)]
+    pub remote: RemoteEngineConfig,
+    #[serde(default)]
+    pub download: DownloadConfig,
+    #[serde(default = "default_chunk_workers")]
+    pub chunk_workers: usize,
+    #[serde(default)]
+    pub output_filter: OutputFilterConfig,
+    #[serde(default)]
+    pub decoding: DecodingConfig,
+    /// Product names, people and jargon fed to whisper as an initial prompt.
+    #[serde(default)]
+    pub vocabulary: Vec<String>,
+    #[serde(default)]
+    pub carry_over_context: bool,
+    /// "transcribe" keeps the spoken language; "translate" delivers English text.
+    #[serde(default = "default_task")]
+    pub task: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptWord {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub probability: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptSegment {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// Only populated when word timestamps are enabled in the config.
+    pub words: Option<Vec<TranscriptWord>>,
+    /// Mean probability of the segment's text tokens.
+    pub confidence: f32,
+    pub min_token_probability: f32,
+    /// Share of the segment's audio that is below the speech energy floor. This measures
+    /// loudness, not recognition quality, so quiet speakers score high on real speech.
+    pub silent_ratio: f32,
+    /// The model's probability that the segment holds no speech, for engines that report it.
+    /// Always `None` for local whisper: the bundled whisper.cpp does not expose the
+    /// `<|nospeech|>` probability, so the no-speech filter only acts on remote output.
+    pub no_speech_probability: Option<f32>,
+    /// Set when a tinydiarize model predicts a different speaker after this segment.
+    pub speaker_turn_next: bool,
+}
+
+/// Live preview sent to the overlay while recording; `is_final` marks the reconciled result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialTranscript {
+    pub session_id: String,
+    pub committed_text: String,
+    pub tentative_text: String,
+    pub is_final: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProgress {
+    pub model_id: String,
+    pub downloaded_bytes: u64,
+    /// Unknown when the server sends neither a length nor a content range.
+    pub total_bytes: Option<u64>,
+    pub bytes_per_sec: f64,
+    pub eta_secs: Option<u64>,
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VerificationStatus {
+    Verified,
+    Missing,
+    SizeMismatch,
+    ChecksumMismatch,
+    /// The file has no manifest entry, so there is nothing to check it against.
+    Unlisted,
+}
+
+impl VerificationStatus {
+    pub fn is_usable(self) -> bool {
+        matches!(self, Self::Verified | Self::Unlisted)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelVerification {
+    pub model_id: String,
+    pub status: VerificationStatus,
+    pub expected_size: Option<u64>,
+    pub actual_size: Option<u64>,
+    pub expected_sha256: Option<String>,
+    pub actual_sha256: Option<String>,
+}
+
+/// Hyperparameters read from the start of a ggml whisper model file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelHeader {
+    pub n_vocab: i32,
+    pub n_audio_layer: i32,
+    pub n_text_layer: i32,
+    pub n_mels: i32,
+    /// Weight type; `ftype % 1000` maps to f32/f16/q4_0/q5_0/... as in whisper.cpp.
+    pub ftype: i32,
+    pub multilingual: bool,
+}
+
+/// A model added by the user, either imported from a local file or downloaded from a URL.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomModel {
+    pub id: String,
+    pub file_name: String,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub imported_from: Option<String>,
+    pub header: ModelHeader,
+}
+
+/// One built-in model build; sizes are estimates for choosing between builds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogModel {
+    pub model_id: String,
+    pub size: String,
+    pub english_only: bool,
+    pub quantization: String,
+    pub file_name: String,
+    pub disk_mb: u32,
+    pub ram_mb: u32,
+    pub installed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PreflightIssueKind {
+    InsufficientDisk,
+    LowDisk,
+    InsufficientMemory,
+    LowMemory,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PreflightSeverity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightIssue {
+    pub kind: PreflightIssueKind,
+    pub severity: PreflightSeverity,
+    pub message: String,
+    pub required_bytes: u64,
+    pub available_bytes: u64,
+}
+
+/// Whether this machine can download and run a model, based on catalog estimates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPreflight {
+    pub model_id: String,
+    pub installed: bool,
+    /// Bytes still to download; `None` for models without a catalog estimate.
+    pub download_bytes: Option<u64>,
+    pub ram_bytes: Option<u64>,
+    pub free_disk_bytes: Option<u64>,
+    pub total_memory_bytes: u64,
+    pub available_memory_bytes: u64,
+    pub issues: Vec<PreflightIssue>,
+}
+
+impl ModelPreflight {
+    pub fn blocking_issue(&self) -> Option<&PreflightIssue> {
+        self.issues
+            .iter()
+            .find(|issue| issue.severity == PreflightSeverity::Error)
+    }
+}
+
+/// A ggml file found in the model root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledModel {
+    pub file_name: String,
+    /// `None` for files that do not belong to a known model id.
+    pub model_id: Option<String>,
+    pub size_bytes: u64,
+    /// Last verification result; `None` if never verified or the file changed since.
+    pub verification: Option<VerificationStatus>,
+    pub last_used_ms: Option<u64>,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialCleanup {
+    pub removed_files: Vec<String>,
+    pub freed_bytes: u64,
+}
+
+/// One installed model run over the reference clip; metrics are `None` when it failed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelBenchmark {
+    pub model_id: String,
+    pub file_name: String,
+    pub load_ms: Option<u64>,
+    pub inference_ms: Option<u64>,
+    /// Inference time divided by clip duration; below 1.0 is faster than real time.
+    pub real_time_factor: Option<f32>,
+    pub word_error_rate: Option<f32>,
+    pub transcript: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkReport {
+    pub clip_duration_ms: u64,
+    pub reference_text: String,
+    pub results: Vec<ModelBenchmark>,
+    pub recommended_model: Option<String>,
+    pub recommendation_reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+/// A finished capture waiting for, or going through, transcription and delivery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionJob {
+    pub job_id: String,
+    pub session_id: String,
+    pub status: JobStatus,
+    pub result: Option<TranscriptionResult>,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,5 +458,13 @@ pub struct TranscriptionResult {
     pub commands_applied: Vec<String>,
     pub latency_ms: u128,
     pub confidence: f32,
+    pub low_confidence: bool,
+    pub segments: Vec<TranscriptSegment>,
     pub delivery: String,
+    pub engine: String,
+    pub task: String,
+    /// Language that was spoken: the detected one in auto mode, otherwise the configured one.
+    pub source_language: Option<String>,
+    pub detected_language: Option<String>,
+    pub language_probability: Option<f32>,
 }
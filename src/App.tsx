@@ -1,16 +1,28 @@
 import { useCallback, useEffect, useMemo, useState } from 'react';
 import {
+  benchmarkModels,
+  cancelModelDownload,
   downloadModel,
   enablePermissions,
   getConfig,
   getStatus,
+  onModelDownloadProgress,
+  onTranscriptionJob,
   resetPermissions,
   setHotkey,
   setModel,
+  setModelVariant,
   setPasteMode,
   toggleRecording
 } from './lib/tauri';
-import type { AppConfig, AppStatus, TranscriptionResult } from './lib/types';
+import type {
+  AppConfig,
+  AppStatus,
+  DownloadProgress,
+  ModelPreflight,
+  ModelQuantization,
+  TranscriptionResult
+} from './lib/types';
 
 const EMPTY_STATUS: AppStatus = {
   recordingState: 'idle',
@@ -24,18 +36,39 @@ const MODEL_OPTIONS = [
   { id: 'tiny', label: 'Tiny', detail: '75 MB, fastest' },
   { id: 'base', label: 'Base', detail: '142 MB' },
   { id: 'small', label: 'Small (Recommended)', detail: '466 MB' },
+  { id: 'small-tdrz', label: 'Small + speaker turns', detail: '466 MB, English, meetings' },
   { id: 'medium', label: 'Medium', detail: '1.5 GB' },
   { id: 'large-v3', label: 'Large-v3', detail: '3.1 GB, highest accuracy' },
   { id: 'turbo', label: 'Turbo', detail: 'Fast large model' }
 ] as const;
+const QUANTIZATION_OPTIONS: { id: ModelQuantization; label: string }[] = [
+  { id: 'f16', label: 'Full precision' },
+  { id: 'q8_0', label: 'Q8_0 - about half the size' },
+  { id: 'q5_1', label: 'Q5_1 - smallest (tiny to small)' },
+  { id: 'q5_0', label: 'Q5_0 - smallest (medium and up)' }
+];
 const PASTE_OPTIONS = ['clipboard', 'auto-paste'] as const;
 
+function preflightNotice(preflight: ModelPreflight): string | null {
+  return preflight.issues.map((issue) => issue.message).join(' ') || null;
+}
+
+function noSpeechSummary(result: TranscriptionResult): string {
+  const reported = result.segments.flatMap((segment) =>
+    segment.noSpeechProbability === null ? [] : [segment.noSpeechProbability]
+  );
+  // Local whisper does not report a no-speech probability.
+  return reported.length ? Math.max(...reported).toFixed(2) : 'n/a';
+}
+
 export function App() {
   const [status, setStatus] = useState<AppStatus>(EMPTY_STATUS);
   const [config, setConfig] = useState<AppConfig | null>(null);
   const [result, setResult] = useState<TranscriptionResult | null>(null);
   const [busy, setBusy] = useState(false);
+  const [download, setDownload] = useState<DownloadProgress | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [notice, setNotice] = useState<string | null>(null);
   const [hotkeyDraft, setHotkeyDraft] = useState('');
   const [startedAt, setStartedAt] = useState<number | null>(null);
   const [elapsedSec, setElapsedSec] = useState(0);
@@ -85,14 +118,25 @@ export function App() {
     return () => window.clearInterval(interval);
   }, [recordingNow, startedAt]);
 
+  useEffect(() => {
+    const unlisten = onTranscriptionJob((job) => {
+      if (job.status === 'done') {
+        setResult(job.result);
+      } else if (job.status === 'failed') {
+        setError(job.error);
+      }
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, []);
+
   const onToggle = useCallback(async () => {
     setError(null);
     setBusy(true);
     try {
-      const transcription = await toggleRecording();
-      if (transcription) {
-        setResult(transcription);
-      } else {
+      const job = await toggleRecording();
+      if (!job) {
         setResult(null);
       }
       await refresh();
@@ -106,9 +150,11 @@ export function App() {
   const onChangeModel = useCallback(
     async (model: AppConfig['modelDefault']) => {
       setError(null);
+      setNotice(null);
       setBusy(true);
       try {
-        await setModel(model);
+        const preflight = await setModel(model);
+        setNotice(preflightNotice(preflight));
         await refresh();
       } catch (e: unknown) {
         setError(e instanceof Error ? e.message : String(e));
@@ -119,6 +165,40 @@ export function App() {
     [refresh]
   );
 
+  const onChangeQuantization = useCallback(
+    async (quantization: ModelQuantization) => {
+      if (!config) {
+        return;
+      }
+      setError(null);
+      setNotice(null);
+      setBusy(true);
+      try {
+        const preflight = await setModelVariant({ ...config.modelVariant, quantization });
+        setNotice(preflightNotice(preflight));
+        await refresh();
+      } catch (e: unknown) {
+        setError(e instanceof Error ? e.message : String(e));
+      } finally {
+        setBusy(false);
+      }
+    },
+    [config, refresh]
+  );
+
+  useEffect(() => {
+    const unlisten = onModelDownloadProgress((progress) => {
+      setDownload(progress.done ? null : progress);
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  const downloadPercent = download?.totalBytes
+    ? Math.floor((download.downloadedBytes / download.totalBytes) * 100)
+    : null;
+
   const onDownload = useCallback(async () => {
     if (!config) return;
     setError(null);
@@ -129,10 +209,34 @@ export function App() {
     } catch (e: unknown) {
       setError(e instanceof Error ? e.message : String(e));
     } finally {
+      setDownload(null);
       setBusy(false);
     }
   }, [config, refresh]);
 
+  const onBenchmark = useCallback(async () => {
+    setError(null);
+    setNotice(null);
+    setBusy(true);
+    try {
+      const report = await benchmarkModels();
+      setNotice(report.recommendationReason);
+    } catch (e: unknown) {
+      setError(e instanceof Error ? e.message : String(e));
+    } finally {
+      setBusy(false);
+    }
+  }, []);
+
+  const onCancelDownload = useCallback(async () => {
+    if (!config) return;
+    try {
+      await cancelModelDownload(config.modelDefault);
+    } catch (e: unknown) {
+      setError(e instanceof Error ? e.message : String(e));
+    }
+  }, [config]);
+
   const onSaveHotkey = useCallback(async () => {
     if (!hotkeyDraft.trim()) return;
     setError(null);
@@ -233,8 +337,16 @@ export function App() {
             {recordingNow ? 'Stop Dictation' : 'Start Dictation'}
           </button>
           <button className="btn btn-secondary" onClick={onDownload} disabled={busy || !config}>
-            Download Current Model
+            {downloadPercent === null ? 'Download Current Model' : `Downloading ${downloadPercent}%`}
+          </button>
+          <button className="btn btn-secondary" onClick={() => void onBenchmark()} disabled={busy}>
+            Benchmark Models
           </button>
+          {download && (
+            <button className="btn btn-secondary" onClick={() => void onCancelDownload()}>
+              Cancel Download
+            </button>
+          )}
         </div>
 
         <div className="actions action-row">
@@ -263,6 +375,36 @@ export function App() {
                   {model.label} - {model.detail}
                 </option>
               ))}
+              {config && !MODEL_OPTIONS.some((model) => model.id === config.modelDefault) ? (
+                <option value={config.modelDefault}>{config.modelDefault}</option>
+              ) : null}
+            </select>
+            {config?.modelRecommendation && !config.modelSelectedByUser ? (
+              <small>Recommended for this machine: {config.modelRecommendation.reason}</small>
+            ) : null}
+            {config?.engine === 'whisper' && config.outputFilter.enabled ? (
+              <small>
+                Local whisper reports no no-speech probability, so silence is not filtered by the
+                no-speech threshold.
+              </small>
+            ) : null}
+          </div>
+
+          <div className="field-group">
+            <label htmlFor="quantization">Precision</label>
+            <select
+              id="quantization"
+              value={config?.modelVariant.quantization ?? 'f16'}
+              onChange={(e) => {
+                void onChangeQuantization(e.target.value as ModelQuantization);
+              }}
+              disabled={busy}
+            >
+              {QUANTIZATION_OPTIONS.map((option) => (
+                <option key={option.id} value={option.id}>
+                  {option.label}
+                </option>
+              ))}
             </select>
           </div>
 
@@ -310,7 +452,7 @@ export function App() {
             <small>
               confidence={result.confidence.toFixed(2)}, latency={result.latencyMs}ms, commands={
                 result.commandsApplied.length
-              }
+              }, noSpeech={noSpeechSummary(result)}
             </small>
             <p>
               {result.delivery === 'auto-paste'
@@ -325,6 +467,12 @@ export function App() {
           </article>
         ) : null}
 
+        {notice ? (
+          <p className="notice" role="status" aria-live="polite">
+            {notice}
+          </p>
+        ) : null}
+
         {statusError ? (
           <p className="error" role="status" aria-live="polite">
             {statusError}
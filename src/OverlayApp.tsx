@@ -1,6 +1,6 @@
 import { useEffect, useMemo, useState } from 'react';
-import { getStatus } from './lib/tauri';
-import type { AppStatus } from './lib/types';
+import { getStatus, onPartialTranscript } from './lib/tauri';
+import type { AppStatus, PartialTranscript } from './lib/types';
 
 const EMPTY_STATUS: AppStatus = {
   recordingState: 'idle',
@@ -14,6 +14,14 @@ const BAR_COUNT = 22;
 
 export function OverlayApp() {
   const [status, setStatus] = useState<AppStatus>(EMPTY_STATUS);
+  const [partial, setPartial] = useState<PartialTranscript | null>(null);
+
+  useEffect(() => {
+    const unlisten = onPartialTranscript(setPartial);
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, []);
 
   useEffect(() => {
     let mounted = true;
@@ -49,6 +57,14 @@ export function OverlayApp() {
 
   const active = mode !== 'idle';
 
+  useEffect(() => {
+    if (mode === 'idle') setPartial(null);
+  }, [mode]);
+
+  const previewText = partial
+    ? [partial.committedText, partial.tentativeText].filter(Boolean).join(' ')
+    : '';
+
   return (
     <main className="overlay-shell" aria-live="polite">
       <section className={`overlay-card ${active ? 'overlay-active' : ''}`}>
@@ -70,6 +86,8 @@ export function OverlayApp() {
         ) : null}
 
         {mode === 'transcribing' ? <div className="overlay-processing">Processing speech...</div> : null}
+
+        {active && previewText ? <p className="overlay-preview">{previewText}</p> : null}
       </section>
     </main>
   );
@@ -1,5 +1,25 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { AppConfig, AppStatus, TranscriptionResult } from './types';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type {
+  AppConfig,
+  AppStatus,
+  BenchmarkReport,
+  CatalogModel,
+  CustomModel,
+  DecodingConfig,
+  DownloadConfig,
+  DownloadProgress,
+  InstalledModel,
+  ModelPreflight,
+  ModelVariant,
+  ModelVerification,
+  OutputFilterConfig,
+  PartialCleanup,
+  PartialTranscript,
+  RemoteEngineConfig,
+  TranscriptionJob,
+  TranscriptionTask
+} from './types';
 
 export async function getStatus(): Promise<AppStatus> {
   return invoke<AppStatus>('get_status');
@@ -9,12 +29,34 @@ export async function startRecording(): Promise<string> {
   return invoke<string>('start_recording');
 }
 
-export async function stopRecording(sessionId: string): Promise<TranscriptionResult> {
-  return invoke<TranscriptionResult>('stop_recording', { sessionId });
+export async function stopRecording(sessionId: string): Promise<TranscriptionJob> {
+  return invoke<TranscriptionJob>('stop_recording', { sessionId });
 }
 
-export async function toggleRecording(): Promise<TranscriptionResult | null> {
-  return invoke<TranscriptionResult | null>('toggle_recording');
+export async function toggleRecording(): Promise<TranscriptionJob | null> {
+  return invoke<TranscriptionJob | null>('toggle_recording');
+}
+
+export async function getTranscriptionJobs(): Promise<TranscriptionJob[]> {
+  return invoke<TranscriptionJob[]>('get_transcription_jobs');
+}
+
+export async function cancelTranscriptionJob(jobId: string): Promise<void> {
+  return invoke('cancel_transcription_job', { jobId });
+}
+
+export async function onTranscriptionJob(
+  handler: (job: TranscriptionJob) => void
+): Promise<UnlistenFn> {
+  return listen<TranscriptionJob>('transcription-job', (event) => handler(event.payload));
+}
+
+export async function cancelRecording(): Promise<void> {
+  return invoke('cancel_recording');
+}
+
+export async function setCancelHotkey(hotkeySpec: string): Promise<void> {
+  return invoke('set_cancel_hotkey', { hotkeySpec });
 }
 
 export async function setHotkey(hotkeySpec: string): Promise<void> {
@@ -34,9 +76,83 @@ export async function enablePermissions(): Promise<void> {
 }
 
 export async function setModel(
-  modelId: 'tiny' | 'base' | 'small' | 'medium' | 'large-v3' | 'turbo'
-): Promise<void> {
-  return invoke('set_model', { modelId });
+  modelId: AppConfig['modelDefault']
+): Promise<ModelPreflight> {
+  return invoke<ModelPreflight>('set_model', { modelId });
+}
+
+export async function setModelVariant(variant: ModelVariant): Promise<ModelPreflight> {
+  return invoke<ModelPreflight>('set_model_variant', { variant });
+}
+
+export async function benchmarkModels(): Promise<BenchmarkReport> {
+  return invoke<BenchmarkReport>('benchmark_models');
+}
+
+export async function checkModel(modelId: AppConfig['modelDefault']): Promise<ModelPreflight> {
+  return invoke<ModelPreflight>('check_model', { modelId });
+}
+
+export async function getModelCatalog(): Promise<CatalogModel[]> {
+  return invoke<CatalogModel[]>('get_model_catalog');
+}
+
+export async function setModelUnloadTimeout(timeoutSecs: number | null): Promise<void> {
+  return invoke('set_model_unload_timeout', { timeoutSecs });
+}
+
+export async function setWordTimestamps(enabled: boolean): Promise<void> {
+  return invoke('set_word_timestamps', { enabled });
+}
+
+export async function setStreamingPreview(enabled: boolean): Promise<void> {
+  return invoke('set_streaming_preview', { enabled });
+}
+
+export async function onPartialTranscript(
+  handler: (transcript: PartialTranscript) => void
+): Promise<UnlistenFn> {
+  return listen<PartialTranscript>('partial-transcript', (event) => handler(event.payload));
+}
+
+export async function setEngine(engine: AppConfig['engine']): Promise<void> {
+  return invoke('set_engine', { engine });
+}
+
+export async function setRemoteEngine(remote: RemoteEngineConfig): Promise<void> {
+  return invoke('set_remote_engine', { remote });
+}
+
+export async function setDownloadConfig(download: DownloadConfig): Promise<void> {
+  return invoke('set_download_config', { download });
+}
+
+export async function setChunkWorkers(workers: number): Promise<void> {
+  return invoke('set_chunk_workers', { workers });
+}
+
+export async function setOutputFilter(filter: OutputFilterConfig): Promise<void> {
+  return invoke('set_output_filter', { filter });
+}
+
+export async function setDecoding(decoding: DecodingConfig): Promise<void> {
+  return invoke('set_decoding', { decoding });
+}
+
+export async function setVocabulary(terms: string[]): Promise<void> {
+  return invoke('set_vocabulary', { terms });
+}
+
+export async function setCarryOverContext(enabled: boolean): Promise<void> {
+  return invoke('set_carry_over_context', { enabled });
+}
+
+export async function setTask(task: TranscriptionTask): Promise<void> {
+  return invoke('set_task', { task });
+}
+
+export async function setLanguage(language: string): Promise<void> {
+  return invoke('set_language', { language });
 }
 
 export async function setPasteMode(pasteMode: 'clipboard' | 'auto-paste'): Promise<void> {
@@ -44,7 +160,58 @@ export async function setPasteMode(pasteMode: 'clipboard' | 'auto-paste'): Promi
 }
 
 export async function downloadModel(
-  modelId: 'tiny' | 'base' | 'small' | 'medium' | 'large-v3' | 'turbo'
+  modelId: AppConfig['modelDefault']
 ): Promise<string> {
   return invoke<string>('download_model', { modelId });
 }
+
+export async function cancelModelDownload(modelId: AppConfig['modelDefault']): Promise<void> {
+  return invoke('cancel_model_download', { modelId });
+}
+
+export async function onModelDownloadProgress(
+  handler: (progress: DownloadProgress) => void
+): Promise<UnlistenFn> {
+  return listen<DownloadProgress>('model-download-progress', (event) => handler(event.payload));
+}
+
+export async function listModels(): Promise<InstalledModel[]> {
+  return invoke<InstalledModel[]>('list_models');
+}
+
+export async function inspectModel(fileName: string): Promise<InstalledModel> {
+  return invoke<InstalledModel>('inspect_model', { fileName });
+}
+
+export async function deleteModel(
+  fileName: string,
+  switchTo: AppConfig['modelDefault'] | null = null
+): Promise<void> {
+  return invoke('delete_model', { fileName, switchTo });
+}
+
+export async function importModel(
+  path: string,
+  modelId: string | null = null,
+  link = false
+): Promise<CustomModel> {
+  return invoke<CustomModel>('import_model', { path, modelId, link });
+}
+
+export async function addModelUrl(
+  modelId: string,
+  url: string,
+  sha256: string | null = null
+): Promise<CustomModel> {
+  return invoke<CustomModel>('add_model_url', { modelId, url, sha256 });
+}
+
+export async function cleanPartialDownloads(): Promise<PartialCleanup> {
+  return invoke<PartialCleanup>('clean_partial_downloads');
+}
+
+export async function verifyModel(
+  modelId: AppConfig['modelDefault']
+): Promise<ModelVerification> {
+  return invoke<ModelVerification>('verify_model', { modelId });
+}
@@ -8,23 +8,262 @@ export interface AppStatus {
   lastError: string | null;
 }
 
+export interface DownloadProgress {
+  modelId: string;
+  downloadedBytes: number;
+  totalBytes: number | null;
+  bytesPerSec: number;
+  etaSecs: number | null;
+  done: boolean;
+}
+
+export type VerificationStatus =
+  | 'verified'
+  | 'missing'
+  | 'sizeMismatch'
+  | 'checksumMismatch'
+  | 'unlisted';
+
+export interface ModelVerification {
+  modelId: string;
+  status: VerificationStatus;
+  expectedSize: number | null;
+  actualSize: number | null;
+  expectedSha256: string | null;
+  actualSha256: string | null;
+}
+
+export type BuiltinModelId =
+  | 'tiny'
+  | 'base'
+  | 'small'
+  | 'small-tdrz'
+  | 'medium'
+  | 'large-v3'
+  | 'turbo';
+
+export type ModelQuantization = 'f16' | 'q5_0' | 'q5_1' | 'q8_0';
+
+/** Unset fields keep the selected size's default build. */
+export interface ModelVariant {
+  quantization: ModelQuantization | null;
+  englishOnly: boolean | null;
+}
+
+export interface ModelRecommendation {
+  modelId: BuiltinModelId;
+  quantization: ModelQuantization | null;
+  reason: string;
+  cpuCores: number;
+  simdFeatures: string[];
+  totalMemoryBytes: number;
+}
+
+export interface CatalogModel {
+  modelId: string;
+  size: BuiltinModelId;
+  englishOnly: boolean;
+  quantization: ModelQuantization;
+  fileName: string;
+  diskMb: number;
+  ramMb: number;
+  installed: boolean;
+}
+
+export interface ModelHeader {
+  nVocab: number;
+  nAudioLayer: number;
+  nTextLayer: number;
+  nMels: number;
+  ftype: number;
+  multilingual: boolean;
+}
+
+export interface CustomModel {
+  id: string;
+  fileName: string;
+  url: string | null;
+  sha256: string | null;
+  importedFrom: string | null;
+  header: ModelHeader;
+}
+
+export type PreflightIssueKind =
+  | 'insufficientDisk'
+  | 'lowDisk'
+  | 'insufficientMemory'
+  | 'lowMemory';
+
+export interface PreflightIssue {
+  kind: PreflightIssueKind;
+  severity: 'warning' | 'error';
+  message: string;
+  requiredBytes: number;
+  availableBytes: number;
+}
+
+export interface ModelPreflight {
+  modelId: string;
+  installed: boolean;
+  downloadBytes: number | null;
+  ramBytes: number | null;
+  freeDiskBytes: number | null;
+  totalMemoryBytes: number;
+  availableMemoryBytes: number;
+  issues: PreflightIssue[];
+}
+
+export interface ModelBenchmark {
+  modelId: string;
+  fileName: string;
+  loadMs: number | null;
+  inferenceMs: number | null;
+  realTimeFactor: number | null;
+  wordErrorRate: number | null;
+  transcript: string | null;
+  error: string | null;
+}
+
+export interface BenchmarkReport {
+  clipDurationMs: number;
+  referenceText: string;
+  results: ModelBenchmark[];
+  recommendedModel: string | null;
+  recommendationReason: string;
+}
+
+export interface InstalledModel {
+  fileName: string;
+  modelId: AppConfig['modelDefault'] | null;
+  sizeBytes: number;
+  verification: VerificationStatus | null;
+  lastUsedMs: number | null;
+  active: boolean;
+}
+
+export interface PartialCleanup {
+  removedFiles: string[];
+  freedBytes: number;
+}
+
+export type JobStatus = 'queued' | 'running' | 'done' | 'failed' | 'cancelled';
+
+export interface TranscriptionJob {
+  jobId: string;
+  sessionId: string;
+  status: JobStatus;
+  result: TranscriptionResult | null;
+  error: string | null;
+}
+
+export interface PartialTranscript {
+  sessionId: string;
+  committedText: string;
+  tentativeText: string;
+  isFinal: boolean;
+}
+
+export interface TranscriptWord {
+  text: string;
+  startMs: number;
+  endMs: number;
+  probability: number;
+}
+
+export interface TranscriptSegment {
+  text: string;
+  startMs: number;
+  endMs: number;
+  words: TranscriptWord[] | null;
+  confidence: number;
+  minTokenProbability: number;
+  silentRatio: number;
+  /** Always null for local whisper, which does not report a no-speech probability. */
+  noSpeechProbability: number | null;
+  speakerTurnNext: boolean;
+}
+
+export type TranscriptionTask = 'transcribe' | 'translate';
+
 export interface TranscriptionResult {
   rawText: string;
   transformedText: string;
   commandsApplied: string[];
   latencyMs: number;
   confidence: number;
+  lowConfidence: boolean;
+  segments: TranscriptSegment[];
   delivery: 'clipboard' | 'auto-paste';
+  engine: string;
+  task: TranscriptionTask;
+  sourceLanguage: string | null;
+  detectedLanguage: string | null;
+  languageProbability: number | null;
+}
+
+export interface RemoteEngineConfig {
+  baseUrl: string;
+  model: string;
+  timeoutSecs: number;
+  fallbackToLocal: boolean;
+}
+
+export interface DownloadConfig {
+  /** Replaces https://huggingface.co for built-in models; empty uses Hugging Face. */
+  mirrorBaseUrl: string;
+  /** Full download URL per model id, taking precedence over the mirror. */
+  urlOverrides: Record<string, string>;
+  /** Empty uses the HTTP_PROXY/HTTPS_PROXY environment. */
+  proxyUrl: string;
+  caCertPaths: string[];
+}
+
+export interface OutputFilterConfig {
+  enabled: boolean;
+  stripAnnotations: boolean;
+  dropStockPhrases: boolean;
+  maxPhraseRepeats: number;
+  /** Only applies to the remote engine; local whisper reports no no-speech probability. */
+  noSpeechThreshold: number;
+}
+
+export interface DecodingConfig {
+  strategy: 'greedy' | 'beam-search';
+  bestOf: number;
+  beamSize: number;
+  temperature: number;
+  temperatureIncrement: number;
+  entropyThreshold: number;
+  logprobThreshold: number;
+  threads: number | null;
 }
 
 export interface AppConfig {
   hotkey: string;
-  modelDefault: 'tiny' | 'base' | 'small' | 'medium' | 'large-v3' | 'turbo';
+  cancelHotkey: string;
+  /** A built-in model id or the id of a registered custom model. */
+  modelDefault: BuiltinModelId | string;
+  modelVariant: ModelVariant;
+  /** Hardware-based pick from first launch; explains the default in settings. */
+  modelRecommendation: ModelRecommendation | null;
+  modelSelectedByUser: boolean;
   commandMode: 'basic';
   pasteMode: 'clipboard' | 'auto-paste';
-  language: 'en';
+  language: string;
   privacy: {
     telemetryEnabled: boolean;
     persistAudioDebug: boolean;
   };
+  modelUnloadAfterSecs: number | null;
+  wordTimestamps: boolean;
+  streamingPreview: boolean;
+  engine: 'whisper' | 'remote' | 'mock';
+  remote: RemoteEngineConfig;
+  download: DownloadConfig;
+  chunkWorkers: number;
+  outputFilter: OutputFilterConfig;
+  decoding: DecodingConfig;
+  vocabulary: string[];
+  carryOverContext: boolean;
+  task: TranscriptionTask;
 }